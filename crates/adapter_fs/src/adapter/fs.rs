@@ -1,3 +1,6 @@
+mod page_content_template_repository;
+mod page_index;
 mod page_repository;
 
+pub use self::page_content_template_repository::*;
 pub use self::page_repository::*;
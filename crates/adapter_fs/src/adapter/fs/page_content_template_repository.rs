@@ -0,0 +1,92 @@
+use std::{fs, path::PathBuf};
+
+use entity::PageContentTemplate;
+use use_case::PageContentTemplateRepository;
+
+// e.g. `{data_dir}/.rust-memo/templates/book.md`
+pub struct FsPageContentTemplateRepository {
+    template_dir: PathBuf,
+}
+
+impl FsPageContentTemplateRepository {
+    pub fn new(data_dir: PathBuf) -> Self {
+        Self {
+            template_dir: data_dir.join(".rust-memo").join("templates"),
+        }
+    }
+}
+
+// rejects paths (e.g. "../foo")
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+impl PageContentTemplateRepository for FsPageContentTemplateRepository {
+    fn find_by_name(&self, name: &str) -> anyhow::Result<Option<PageContentTemplate>> {
+        if !is_valid_name(name) {
+            return Ok(None);
+        }
+        let file_name = self.template_dir.join(format!("{}.md", name));
+        if !file_name.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(file_name)?;
+        Ok(Some(PageContentTemplate::new(name.to_string(), content)))
+    }
+
+    fn find_names(&self) -> anyhow::Result<Vec<String>> {
+        if !self.template_dir.exists() {
+            return Ok(vec![]);
+        }
+        let mut names = vec![];
+        for res in fs::read_dir(self.template_dir.as_path())? {
+            let path = res?.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("md") {
+                continue;
+            }
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                if is_valid_name(name) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageContentTemplateRepository::new(data_dir.clone());
+        assert_eq!(repository.find_names()?, Vec::<String>::new());
+        assert_eq!(repository.find_by_name("book")?, None);
+
+        let template_dir = data_dir.join(".rust-memo").join("templates");
+        fs::create_dir_all(template_dir.as_path())?;
+        fs::write(template_dir.join("book.md"), "# {{title}}")?;
+        fs::write(template_dir.join("daily.md"), "# {{date}}")?;
+        fs::write(template_dir.join("notes.txt"), "")?;
+        fs::write(data_dir.join("secret.md"), "")?;
+        assert_eq!(repository.find_names()?, vec!["book", "daily"]);
+        assert_eq!(
+            repository.find_by_name("book")?,
+            Some(PageContentTemplate::new(
+                "book".to_string(),
+                "# {{title}}".to_string()
+            ))
+        );
+        assert_eq!(repository.find_by_name("../../secret")?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,510 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::UNIX_EPOCH,
+};
+
+use entity::{Page, PageId, QueryExpr, TextMatcher};
+
+fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3040..=0x30FF // Hiragana, Katakana
+            | 0x3400..=0x4DBF // CJK Unified Ideographs Extension A
+            | 0x4E00..=0x9FFF // CJK Unified Ideographs
+            | 0xAC00..=0xD7AF // Hangul Syllables
+            | 0xF900..=0xFAFF // CJK Compatibility Ideographs
+            | 0xFF66..=0xFF9F // Halfwidth Katakana
+    )
+}
+
+fn push_run(tokens: &mut BTreeSet<String>, run: &[char], cjk: bool) {
+    if run.is_empty() {
+        return;
+    }
+    if cjk && run.len() > 1 {
+        for bigram in run.windows(2) {
+            tokens.insert(bigram.iter().collect::<String>());
+        }
+    } else {
+        tokens.insert(run.iter().collect::<String>());
+    }
+}
+
+// words (lowercased) and CJK bigrams
+pub fn tokenize(s: &str) -> BTreeSet<String> {
+    let mut tokens = BTreeSet::new();
+    let mut run = vec![];
+    let mut run_is_cjk = false;
+    for c in s.chars().flat_map(char::to_lowercase) {
+        if is_cjk(c) {
+            if !run_is_cjk {
+                push_run(&mut tokens, &run, run_is_cjk);
+                run.clear();
+                run_is_cjk = true;
+            }
+            run.push(c);
+        } else if c.is_alphanumeric() {
+            if run_is_cjk {
+                push_run(&mut tokens, &run, run_is_cjk);
+                run.clear();
+                run_is_cjk = false;
+            }
+            run.push(c);
+        } else {
+            push_run(&mut tokens, &run, run_is_cjk);
+            run.clear();
+        }
+    }
+    push_run(&mut tokens, &run, run_is_cjk);
+    tokens
+}
+
+// the suffixes are truncated, so that a long token doesn't make O(len^2) keys
+const MAX_SUFFIX_CHARS: usize = 16;
+
+fn truncate(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((i, _)) => &s[..i],
+        None => s,
+    }
+}
+
+// e.g. "foo" -> "foo", "oo", "o"
+fn suffixes(token: &str) -> impl Iterator<Item = &str> {
+    token
+        .char_indices()
+        .map(move |(i, _)| truncate(&token[i..], MAX_SUFFIX_CHARS))
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FileStamp {
+    len: u64,
+    modified_nanos: u128,
+}
+
+impl FileStamp {
+    pub fn from_metadata(metadata: &fs::Metadata) -> anyhow::Result<Self> {
+        Ok(Self {
+            len: metadata.len(),
+            modified_nanos: metadata.modified()?.duration_since(UNIX_EPOCH)?.as_nanos(),
+        })
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+impl std::fmt::Display for FileStamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.modified_nanos, self.len)
+    }
+}
+
+impl std::str::FromStr for FileStamp {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modified_nanos, len) = s
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("invalid file stamp: {}", s))?;
+        Ok(Self {
+            len: len.parse()?,
+            modified_nanos: modified_nanos.parse()?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    // None if the indexed content may differ from the file
+    stamp: Option<FileStamp>,
+    tokens: BTreeSet<String>,
+}
+
+// An inverted index persisted as an append-only log.
+//
+// "+\t{page_id}\t{stamp or -}\t{token} {token} ..." adds (or replaces) a page
+// "-\t{page_id}" removes a page
+#[derive(Debug)]
+pub struct PageIndex {
+    // the stamp of the data dir when the pages were last checked (not persisted)
+    dir_stamp: Option<FileStamp>,
+    entries: BTreeMap<PageId, Entry>,
+    log_lines: usize,
+    path: PathBuf,
+    // keyed by the (truncated) suffixes of the tokens, so a substring of a token is a prefix of a key
+    postings: BTreeMap<String, BTreeSet<PageId>>,
+}
+
+impl PageIndex {
+    pub fn open(path: PathBuf) -> anyhow::Result<Self> {
+        let mut index = Self {
+            dir_stamp: None,
+            entries: BTreeMap::new(),
+            log_lines: 0,
+            path,
+            postings: BTreeMap::new(),
+        };
+        if !index.path.exists() {
+            return Ok(index);
+        }
+        let file = fs::File::open(index.path.as_path())?;
+        for line in BufReader::new(file).lines() {
+            let line = line?;
+            index.log_lines += 1;
+            let mut columns = line.split('\t');
+            match (columns.next(), columns.next().map(PageId::from_str)) {
+                (Some("+"), Some(Ok(page_id))) => {
+                    let stamp = columns.next().and_then(|s| FileStamp::from_str(s).ok());
+                    let tokens = columns
+                        .next()
+                        .unwrap_or_default()
+                        .split(' ')
+                        .filter(|s| !s.is_empty())
+                        .map(|s| s.to_string())
+                        .collect::<BTreeSet<String>>();
+                    index.insert(page_id, stamp, tokens);
+                }
+                (Some("-"), Some(Ok(page_id))) => index.remove(&page_id),
+                // ignore broken lines (e.g. a crash while appending)
+                _ => continue,
+            }
+        }
+        if index.log_lines > index.entries.len() * 2 + 100 {
+            index.compact()?;
+        }
+        Ok(index)
+    }
+
+    pub fn add_page(&mut self, page: &Page, stamp: Option<FileStamp>) -> anyhow::Result<()> {
+        let tokens = tokenize(page.content().as_str());
+        self.append(&format!(
+            "+\t{}\t{}\t{}",
+            page.id(),
+            stamp
+                .map(|stamp| stamp.to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            tokens.iter().cloned().collect::<Vec<String>>().join(" ")
+        ))?;
+        self.insert(*page.id(), stamp, tokens);
+        Ok(())
+    }
+
+    // None if the query can't be narrowed down by the index
+    pub fn candidates(&self, expr: &QueryExpr) -> Option<BTreeSet<PageId>> {
+        match expr {
+            QueryExpr::And(exprs) => exprs.iter().filter_map(|expr| self.candidates(expr)).fold(
+                None,
+                |acc: Option<BTreeSet<PageId>>, ids| match acc {
+                    None => Some(ids),
+                    Some(acc) => Some(acc.intersection(&ids).copied().collect()),
+                },
+            ),
+            QueryExpr::Or(exprs) => {
+                let mut ids = BTreeSet::new();
+                for expr in exprs {
+                    ids.extend(self.candidates(expr)?);
+                }
+                Some(ids)
+            }
+            // the title is the first line of the content
+            QueryExpr::Text(TextMatcher::Literal(s))
+            | QueryExpr::Title(TextMatcher::Literal(s)) => self.term_candidates(s),
+            QueryExpr::Not(_)
+            | QueryExpr::Text(_)
+            | QueryExpr::Title(_)
+            | QueryExpr::LinksTo(_)
+            | QueryExpr::Obsoleted(_)
+            | QueryExpr::Created(_, _) => None,
+        }
+    }
+
+    pub fn clear(&mut self) -> anyhow::Result<()> {
+        self.dir_stamp = None;
+        self.entries.clear();
+        self.postings.clear();
+        self.compact()
+    }
+
+    pub fn dir_stamp(&self) -> Option<FileStamp> {
+        self.dir_stamp
+    }
+
+    pub fn page_ids(&self) -> BTreeSet<PageId> {
+        self.entries.keys().copied().collect()
+    }
+
+    pub fn remove_page(&mut self, page_id: &PageId) -> anyhow::Result<()> {
+        if !self.entries.contains_key(page_id) {
+            return Ok(());
+        }
+        self.append(&format!("-\t{}", page_id))?;
+        self.remove(page_id);
+        Ok(())
+    }
+
+    pub fn set_dir_stamp(&mut self, dir_stamp: Option<FileStamp>) {
+        self.dir_stamp = dir_stamp;
+    }
+
+    pub fn stamp(&self, page_id: &PageId) -> Option<FileStamp> {
+        self.entries.get(page_id).and_then(|entry| entry.stamp)
+    }
+
+    fn append(&mut self, line: &str) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path.as_path())?;
+        writeln!(file, "{}", line)?;
+        self.log_lines += 1;
+        Ok(())
+    }
+
+    fn compact(&mut self) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut content = String::new();
+        for (page_id, entry) in self.entries.iter() {
+            content.push_str(&format!(
+                "+\t{}\t{}\t{}\n",
+                page_id,
+                entry
+                    .stamp
+                    .map(|stamp| stamp.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                entry
+                    .tokens
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ));
+        }
+        let temp_path = self.path.with_extension("tmp");
+        fs::write(temp_path.as_path(), content)?;
+        fs::rename(temp_path.as_path(), self.path.as_path())?;
+        self.log_lines = self.entries.len();
+        Ok(())
+    }
+
+    fn insert(&mut self, page_id: PageId, stamp: Option<FileStamp>, tokens: BTreeSet<String>) {
+        self.remove(&page_id);
+        for suffix in tokens.iter().flat_map(|token| suffixes(token)) {
+            self.postings
+                .entry(suffix.to_string())
+                .or_default()
+                .insert(page_id);
+        }
+        self.entries.insert(page_id, Entry { stamp, tokens });
+    }
+
+    fn remove(&mut self, page_id: &PageId) {
+        if let Some(entry) = self.entries.remove(page_id) {
+            for suffix in entry.tokens.iter().flat_map(|token| suffixes(token)) {
+                if let Some(page_ids) = self.postings.get_mut(suffix) {
+                    page_ids.remove(page_id);
+                    if page_ids.is_empty() {
+                        self.postings.remove(suffix);
+                    }
+                }
+            }
+        }
+    }
+
+    fn term_candidates(&self, term: &str) -> Option<BTreeSet<PageId>> {
+        let tokens = tokenize(term);
+        if tokens.is_empty() {
+            return None;
+        }
+        // a token in the term may be a part of a token in the content
+        // (e.g. "oo" in "foo", "あ" in "あい")
+        let mut ids: Option<BTreeSet<PageId>> = None;
+        for token in tokens {
+            // a long token is looked up by its prefix (the pages are narrowed down to a superset)
+            let token = truncate(token.as_str(), MAX_SUFFIX_CHARS).to_string();
+            let token_ids = self
+                .postings
+                .range(token.clone()..)
+                .take_while(|(suffix, _)| suffix.starts_with(token.as_str()))
+                .flat_map(|(_, page_ids)| page_ids.iter().copied())
+                .collect::<BTreeSet<PageId>>();
+            ids = Some(match ids {
+                None => token_ids,
+                Some(ids) => ids.intersection(&token_ids).copied().collect(),
+            });
+        }
+        ids
+    }
+}
+
+pub fn index_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(".rust-memo").join("index")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use entity::{PageContent, Query};
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn set(s: &[&str]) -> BTreeSet<String> {
+        s.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn tokenize_test() {
+        assert!(tokenize("").is_empty());
+        assert_eq!(tokenize("Foo bar, foo."), set(&["foo", "bar"]));
+        assert_eq!(tokenize("あいう"), set(&["あい", "いう"]));
+        assert_eq!(tokenize("あ"), set(&["あ"]));
+        assert_eq!(tokenize("Rustで書く"), set(&["rust", "で書", "書く"]));
+        assert_eq!(tokenize("# 本 x2"), set(&["本", "x2"]));
+    }
+
+    #[test]
+    fn candidates_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let mut index = PageIndex::open(index_path(temp_dir.path()))?;
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        index.add_page(
+            &Page::new(
+                page_id1,
+                PageContent::from("# foo\n\nbar あいう".to_string()),
+            ),
+            None,
+        )?;
+        index.add_page(
+            &Page::new(page_id2, PageContent::from("# baz\n\nbar".to_string())),
+            None,
+        )?;
+        let candidates = |index: &PageIndex, s: &str| -> anyhow::Result<Option<Vec<PageId>>> {
+            Ok(index
+                .candidates(Query::from_str(s)?.expr())
+                .map(|ids| ids.into_iter().collect()))
+        };
+        assert_eq!(candidates(&index, "foo")?, Some(vec![page_id1]));
+        assert_eq!(candidates(&index, "oo")?, Some(vec![page_id1]));
+        assert_eq!(candidates(&index, "BAR")?, Some(vec![page_id1, page_id2]));
+        assert_eq!(candidates(&index, "いう")?, Some(vec![page_id1]));
+        assert_eq!(candidates(&index, "う")?, Some(vec![page_id1]));
+        assert_eq!(candidates(&index, "qux")?, Some(vec![]));
+        assert_eq!(
+            candidates(&index, "foo OR baz")?,
+            Some(vec![page_id1, page_id2])
+        );
+        assert_eq!(
+            candidates(&index, "bar -foo")?,
+            Some(vec![page_id1, page_id2])
+        );
+        assert_eq!(candidates(&index, "title:baz")?, Some(vec![page_id2]));
+        assert_eq!(candidates(&index, "-foo")?, None);
+        assert_eq!(candidates(&index, "foo OR -baz")?, None);
+        assert_eq!(candidates(&index, "!!")?, None);
+
+        index.remove_page(&page_id1)?;
+        assert_eq!(candidates(&index, "bar")?, Some(vec![page_id2]));
+        Ok(())
+    }
+
+    #[test]
+    fn candidates_long_token_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let mut index = PageIndex::open(index_path(temp_dir.path()))?;
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let long = "abcdefghijklmnopqrstuvwxyz".repeat(4);
+        index.add_page(&Page::new(page_id1, PageContent::from(long.clone())), None)?;
+        index.add_page(
+            &Page::new(
+                page_id2,
+                PageContent::from("abcdefghijklmnopqrstu".to_string()),
+            ),
+            None,
+        )?;
+        assert!(index.postings.keys().all(|key| key.chars().count() <= 16));
+        assert_eq!(
+            index.term_candidates(&long),
+            Some(vec![page_id1, page_id2].into_iter().collect())
+        );
+        assert_eq!(
+            index.term_candidates(&long[20..50]),
+            Some(vec![page_id1].into_iter().collect())
+        );
+        assert_eq!(
+            index.term_candidates("xyzabc"),
+            Some(vec![page_id1].into_iter().collect())
+        );
+        assert_eq!(
+            index.term_candidates("pqrstu"),
+            Some(vec![page_id1, page_id2].into_iter().collect())
+        );
+
+        index.remove_page(&page_id1)?;
+        assert_eq!(
+            index.term_candidates(&long),
+            Some(vec![page_id2].into_iter().collect())
+        );
+        index.remove_page(&page_id2)?;
+        assert!(index.postings.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn open_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let path = index_path(temp_dir.path());
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let stamp = FileStamp::from_str("123:4")?;
+        {
+            let mut index = PageIndex::open(path.clone())?;
+            index.add_page(
+                &Page::new(page_id1, PageContent::from("foo".to_string())),
+                Some(stamp),
+            )?;
+            index.add_page(
+                &Page::new(page_id2, PageContent::from("bar".to_string())),
+                None,
+            )?;
+            index.add_page(
+                &Page::new(page_id2, PageContent::from("baz".to_string())),
+                None,
+            )?;
+        }
+        let mut index = PageIndex::open(path.clone())?;
+        assert_eq!(
+            index.page_ids(),
+            vec![page_id1, page_id2].into_iter().collect()
+        );
+        assert_eq!(index.stamp(&page_id1), Some(stamp));
+        assert_eq!(index.stamp(&page_id2), None);
+        assert_eq!(index.term_candidates("bar"), Some(BTreeSet::new()));
+        assert_eq!(
+            index.term_candidates("baz"),
+            Some(vec![page_id2].into_iter().collect())
+        );
+
+        index.remove_page(&page_id1)?;
+        let mut index = PageIndex::open(path.clone())?;
+        assert_eq!(index.page_ids(), vec![page_id2].into_iter().collect());
+
+        index.clear()?;
+        let index = PageIndex::open(path)?;
+        assert!(index.page_ids().is_empty());
+        Ok(())
+    }
+}
@@ -1,13 +1,40 @@
 use std::{
     collections::BTreeSet,
     fs,
-    path::PathBuf,
+    io::{self, Write},
+    path::{Path, PathBuf},
     str::FromStr,
-    sync::{Arc, Mutex},
+    sync::{Arc, Mutex, MutexGuard, RwLock},
+    time::Duration,
 };
 
-use entity::{Page, PageContent, PageGraph, PageId, PageTitle};
-use use_case::PageRepository;
+use anyhow::anyhow;
+use entity::{Page, PageContent, PageGraph, PageId, PageTitle, Query};
+use tempfile::NamedTempFile;
+use use_case::{LockPoisonedError, PageObsoletedError, PageRepository, PageTitledError};
+
+use super::page_index::{index_path, FileStamp, PageIndex};
+
+fn read_ids(dir: &Path) -> anyhow::Result<Vec<PageId>> {
+    let mut ids = vec![];
+    for res in fs::read_dir(dir)? {
+        let dir_entry = res?;
+        let file_type = dir_entry.file_type()?;
+        if !file_type.is_file() {
+            continue;
+        }
+        let path = dir_entry.path();
+        let id_as_string = match path.file_stem().and_then(|os_str| os_str.to_str()) {
+            Some(x) => x,
+            None => continue,
+        };
+        if let Ok(page_id) = PageId::from_str(id_as_string) {
+            ids.push(page_id);
+        }
+    }
+    ids.sort();
+    Ok(ids)
+}
 
 // TODO: returns PathBuf
 pub fn to_file_name(page_id: &PageId) -> String {
@@ -16,28 +43,231 @@ pub fn to_file_name(page_id: &PageId) -> String {
 
 pub struct FsPageRepository {
     data_dir: PathBuf,
-    page_graph: Arc<Mutex<PageGraph>>,
+    // None until loaded (cold)
+    // shared with the readers (copied on write if a reader holds it)
+    page_graph: Arc<RwLock<Option<Arc<PageGraph>>>>,
+    // loaded on first use
+    page_index: Arc<Mutex<Option<PageIndex>>>,
+    // held while writing the page files (`save_if` reads and writes under it)
+    write_lock: Arc<Mutex<()>>,
 }
 
 impl FsPageRepository {
     pub fn new(data_dir: PathBuf) -> Self {
         Self {
             data_dir,
-            page_graph: Arc::new(Mutex::new(PageGraph::default())),
+            page_graph: Arc::new(RwLock::new(None)),
+            page_index: Arc::new(Mutex::new(None)),
+            write_lock: Arc::new(Mutex::new(())),
         }
     }
+
+    fn lock_writes(&self) -> anyhow::Result<MutexGuard<'_, ()>> {
+        Ok(self
+            .write_lock
+            .lock()
+            .map_err(|_| LockPoisonedError("page files"))?)
+    }
+
+    fn file_stamp(&self, page_id: &PageId) -> anyhow::Result<Option<FileStamp>> {
+        let file_name = to_file_name(page_id);
+        let file_name = self.data_dir.join(file_name.as_str());
+        Ok(if file_name.exists() {
+            Some(FileStamp::from_metadata(&fs::metadata(file_name)?)?)
+        } else {
+            None
+        })
+    }
+
+    fn index_page(&self, page: &Page) -> anyhow::Result<()> {
+        // the cached content may differ from the file (e.g. on create events)
+        let stamp = self
+            .file_stamp(page.id())?
+            .filter(|stamp| stamp.len() == page.content().as_str().len() as u64);
+        self.with_page_index(|page_index| {
+            if stamp.is_some() && page_index.stamp(page.id()) == stamp {
+                return Ok(());
+            }
+            page_index.add_page(page, stamp)
+        })
+    }
+
+    // re-indexes the pages modified since they were indexed
+    // `save_cache` (and the watcher of the server) keeps the index up to date in this process,
+    // so the files are checked only on first use and when the data dir has changed
+    fn refresh_index(&self) -> anyhow::Result<()> {
+        let metadata = fs::metadata(self.data_dir.as_path())?;
+        let dir_stamp = FileStamp::from_metadata(&metadata)?;
+        if self.with_page_index(|page_index| Ok(page_index.dir_stamp() == Some(dir_stamp)))? {
+            return Ok(());
+        }
+        // a change in the same tick of the clock doesn't change the stamp (check again next time)
+        let is_racy = metadata
+            .modified()?
+            .elapsed()
+            .map_or(true, |elapsed| elapsed < Duration::from_secs(1));
+        let page_ids = self.find_ids()?;
+        // stamp before reading so that a concurrent write is detected next time
+        let mut stamps = vec![];
+        for page_id in page_ids.iter() {
+            stamps.push((*page_id, self.file_stamp(page_id)?));
+        }
+        let modified = self.with_page_index(|page_index| {
+            Ok(stamps
+                .into_iter()
+                .filter(|(page_id, stamp)| stamp.is_none() || page_index.stamp(page_id) != *stamp)
+                .collect::<Vec<(PageId, Option<FileStamp>)>>())
+        })?;
+        // read the files without holding the lock
+        let mut pages = vec![];
+        for (page_id, stamp) in modified {
+            if let Some(page) = self.find_by_id(&page_id)? {
+                pages.push((page, stamp));
+            }
+        }
+        self.with_page_index(|page_index| {
+            for (page, stamp) in pages.iter() {
+                page_index.add_page(page, *stamp)?;
+            }
+            let existing = page_ids.iter().copied().collect::<BTreeSet<PageId>>();
+            for page_id in page_index.page_ids().difference(&existing) {
+                page_index.remove_page(page_id)?;
+            }
+            page_index.set_dir_stamp(if is_racy { None } else { Some(dir_stamp) });
+            Ok(())
+        })
+    }
+
+    fn load_page_graph_from_files(&self) -> anyhow::Result<PageGraph> {
+        let mut page_graph = PageGraph::default();
+        for page_id in self.find_ids()? {
+            if let Some(page) = self.find_by_id(&page_id)? {
+                page_graph.add_page(page);
+            }
+        }
+        Ok(page_graph)
+    }
+
+    // e.g. `{data_dir}/.rust-memo/trash/{id}.md`
+    fn trash_dir(&self) -> PathBuf {
+        self.data_dir.join(".rust-memo").join("trash")
+    }
+
+    // writes a temp file and renames it, so the file is never half-written
+    // returns false if the file exists and overwrite is false
+    fn write_page_file(&self, page: &Page, overwrite: bool) -> anyhow::Result<bool> {
+        let file_name = to_file_name(page.id());
+        let file_name = self.data_dir.join(file_name.as_str());
+        // the temp file name (".tmpXXXXXX") is not a page id, so the watcher ignores it
+        let mut temp_file = NamedTempFile::new_in(self.data_dir.as_path())?;
+        temp_file.write_all(String::from(page.content().clone()).as_bytes())?;
+        temp_file.as_file().sync_all()?;
+        if overwrite {
+            temp_file.persist(file_name)?;
+        } else if let Err(e) = temp_file.persist_noclobber(file_name) {
+            if e.error.kind() == io::ErrorKind::AlreadyExists {
+                return Ok(false);
+            }
+            return Err(e.into());
+        }
+        // makes the rename durable
+        #[cfg(unix)]
+        fs::File::open(self.data_dir.as_path())?.sync_all()?;
+        Ok(true)
+    }
+
+    fn with_page_graph<T>(&self, f: impl FnOnce(&Arc<PageGraph>) -> T) -> anyhow::Result<T> {
+        if let Some(page_graph) = self
+            .page_graph
+            .read()
+            .map_err(|_| LockPoisonedError("page graph"))?
+            .as_ref()
+        {
+            return Ok(f(page_graph));
+        }
+        // hold the lock while loading so that no update is lost
+        let mut page_graph = self
+            .page_graph
+            .write()
+            .map_err(|_| LockPoisonedError("page graph"))?;
+        if page_graph.is_none() {
+            *page_graph = Some(Arc::new(self.load_page_graph_from_files()?));
+        }
+        Ok(f(page_graph.as_ref().expect("page graph is loaded")))
+    }
+
+    fn with_page_index<T>(
+        &self,
+        f: impl FnOnce(&mut PageIndex) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let mut page_index = self
+            .page_index
+            .lock()
+            .map_err(|_| LockPoisonedError("page index"))?;
+        if page_index.is_none() {
+            *page_index = Some(PageIndex::open(index_path(self.data_dir.as_path()))?);
+        }
+        f(page_index.as_mut().expect("page index is loaded"))
+    }
 }
 
 impl PageRepository for FsPageRepository {
-    fn destroy_cache(&self, page_id: &PageId) -> anyhow::Result<bool> {
-        let mut page_graph = self.page_graph.lock().unwrap(); // TODO
-                                                              // TODO: contains
-        if page_graph.title(page_id).is_some() {
-            page_graph.remove_page(page_id);
-            Ok(true)
-        } else {
-            Ok(false)
+    fn create(&self, page: Page) -> anyhow::Result<bool> {
+        let _write_lock = self.lock_writes()?;
+        // fails if the file exists, even if it is created by another process
+        if !self.write_page_file(&page, false)? {
+            return Ok(false);
         }
+        self.save_cache(page)?;
+        Ok(true)
+    }
+
+    fn create_if_not_obsoleted(&self, page: Page, obsoleted: &PageId) -> anyhow::Result<bool> {
+        let _write_lock = self.lock_writes()?;
+        if self.with_page_graph(|page_graph| page_graph.is_obsoleted(obsoleted))? {
+            return Err(PageObsoletedError(*obsoleted).into());
+        }
+        if !self.write_page_file(&page, false)? {
+            return Ok(false);
+        }
+        self.save_cache(page)?;
+        Ok(true)
+    }
+
+    fn create_if_untitled(&self, page: Page) -> anyhow::Result<bool> {
+        let _write_lock = self.lock_writes()?;
+        let page_title = page.title();
+        let titled = self.with_page_graph(|page_graph| {
+            page_graph
+                .titled(&page_title)
+                .into_iter()
+                .filter(|page_id| !page_graph.is_obsoleted(page_id))
+                .max()
+        })?;
+        if let Some(page_id) = titled {
+            return Err(PageTitledError(page_id).into());
+        }
+        if !self.write_page_file(&page, false)? {
+            return Ok(false);
+        }
+        self.save_cache(page)?;
+        Ok(true)
+    }
+
+    fn destroy_cache(&self, page_id: &PageId) -> anyhow::Result<bool> {
+        self.with_page_index(|page_index| page_index.remove_page(page_id))?;
+        let mut page_graph = self
+            .page_graph
+            .write()
+            .map_err(|_| LockPoisonedError("page graph"))?;
+        Ok(match page_graph.as_mut() {
+            // TODO: contains
+            Some(page_graph) if page_graph.title(page_id).is_some() => {
+                Arc::make_mut(page_graph).remove_page(page_id);
+                true
+            }
+            _ => false,
+        })
     }
 
     fn destroy(&self, page_id: &PageId) -> anyhow::Result<bool> {
@@ -46,6 +276,7 @@ impl PageRepository for FsPageRepository {
         let file_name = self.data_dir.join(file_name.as_str());
         Ok(if file_name.exists() {
             fs::remove_file(file_name)?;
+            self.destroy_cache(page_id)?;
             true
         } else {
             false
@@ -66,54 +297,151 @@ impl PageRepository for FsPageRepository {
         })
     }
 
-    fn find_by_obsoleted(&self, page_id: &PageId) -> BTreeSet<PageId> {
-        self.page_graph.lock().unwrap().obsoleted_by(page_id)
+    fn find_by_obsoleted(&self, page_id: &PageId) -> anyhow::Result<BTreeSet<PageId>> {
+        self.with_page_graph(|page_graph| page_graph.obsoleted_by(page_id))
     }
 
     // TODO: use iterator
     fn find_by_title(&self, page_title: &PageTitle) -> anyhow::Result<Vec<Option<Page>>> {
-        self.page_graph
-            .lock()
-            .unwrap() // TODO
-            .titled(page_title)
+        let page_ids = self.with_page_graph(|page_graph| page_graph.titled(page_title))?;
+        // read the files without holding the lock
+        page_ids
             .into_iter()
             .map(|page_id| self.find_by_id(&page_id))
             .collect::<anyhow::Result<Vec<Option<Page>>>>()
     }
 
+    fn find_candidate_ids(&self, query: &Query) -> anyhow::Result<Vec<PageId>> {
+        self.refresh_index()?;
+        let candidates =
+            self.with_page_index(|page_index| Ok(page_index.candidates(query.expr())))?;
+        match candidates {
+            Some(candidates) => Ok(candidates.into_iter().collect()),
+            None => self.find_ids(),
+        }
+    }
+
     fn find_ids(&self) -> anyhow::Result<Vec<PageId>> {
-        let mut ids = vec![];
-        for res in fs::read_dir(self.data_dir.as_path())? {
-            let dir_entry = res?;
-            let file_type = dir_entry.file_type()?;
-            if !file_type.is_file() {
-                continue;
-            }
-            let path = dir_entry.path();
-            let id_as_string = match path.file_stem().and_then(|os_str| os_str.to_str()) {
-                Some(x) => x,
-                None => continue,
-            };
-            if let Ok(page_id) = PageId::from_str(id_as_string) {
-                ids.push(page_id);
+        read_ids(self.data_dir.as_path())
+    }
+
+    fn find_trashed_by_id(&self, page_id: &PageId) -> anyhow::Result<Option<Page>> {
+        let file_name = self.trash_dir().join(to_file_name(page_id));
+        Ok(if file_name.exists() {
+            let page_content = PageContent::from(fs::read_to_string(file_name)?);
+            Some(Page::new(*page_id, page_content))
+        } else {
+            None
+        })
+    }
+
+    fn find_trashed_ids(&self) -> anyhow::Result<Vec<PageId>> {
+        let trash_dir = self.trash_dir();
+        if !trash_dir.exists() {
+            return Ok(vec![]);
+        }
+        read_ids(trash_dir.as_path())
+    }
+
+    fn is_cache_consistent(&self) -> anyhow::Result<bool> {
+        let page_graph = self
+            .page_graph
+            .read()
+            .map_err(|_| LockPoisonedError("page graph"))?
+            .clone();
+        Ok(match page_graph {
+            None => true,
+            Some(page_graph) => *page_graph == self.load_page_graph_from_files()?,
+        })
+    }
+
+    fn load_page_graph(&self) -> anyhow::Result<Arc<PageGraph>> {
+        self.with_page_graph(Arc::clone)
+    }
+
+    fn purge(&self, page_id: &PageId) -> anyhow::Result<bool> {
+        let file_name = self.trash_dir().join(to_file_name(page_id));
+        Ok(if file_name.exists() {
+            fs::remove_file(file_name)?;
+            true
+        } else {
+            false
+        })
+    }
+
+    fn reindex(&self) -> anyhow::Result<()> {
+        self.with_page_index(|page_index| page_index.clear())?;
+        self.refresh_index()?;
+        Ok(())
+    }
+
+    fn restore(&self, page_id: &PageId) -> anyhow::Result<bool> {
+        let _write_lock = self.lock_writes()?;
+        let trashed = self.trash_dir().join(to_file_name(page_id));
+        if !trashed.exists() {
+            return Ok(false);
+        }
+        let file_name = self.data_dir.join(to_file_name(page_id));
+        // fails if the file exists, even if it is created by another process
+        if let Err(e) = fs::hard_link(trashed.as_path(), file_name) {
+            if e.kind() == io::ErrorKind::AlreadyExists {
+                return Err(anyhow!("page already exists: {}", page_id));
             }
+            return Err(e.into());
         }
-        ids.sort();
-        Ok(ids)
+        fs::remove_file(trashed)?;
+        if let Some(page) = self.find_by_id(page_id)? {
+            self.save_cache(page)?;
+        }
+        Ok(true)
     }
 
     fn save_cache(&self, page: Page) -> anyhow::Result<()> {
-        self.destroy_cache(page.id())?;
-        let mut page_graph = self.page_graph.lock().unwrap(); // TODO
-        page_graph.add_page(page);
+        self.index_page(&page)?;
+        // replace the page at once so that readers never see it missing
+        let mut page_graph = self
+            .page_graph
+            .write()
+            .map_err(|_| LockPoisonedError("page graph"))?;
+        // the page will be loaded with the others when the cache is cold
+        if let Some(page_graph) = page_graph.as_mut() {
+            let page_graph = Arc::make_mut(page_graph);
+            if page_graph.title(page.id()).is_some() {
+                page_graph.remove_page(page.id());
+            }
+            page_graph.add_page(page);
+        }
         Ok(())
     }
 
     fn save(&self, page: Page) -> anyhow::Result<()> {
-        self.save_cache(page.clone())?;
-        let file_name = to_file_name(page.id());
-        let file_name = self.data_dir.join(file_name.as_str());
-        Ok(fs::write(file_name, String::from(page.content().clone()))?)
+        let _write_lock = self.lock_writes()?;
+        self.write_page_file(&page, true)?;
+        self.save_cache(page)
+    }
+
+    fn save_if(&self, page: Page, expected: &PageContent) -> anyhow::Result<bool> {
+        let _write_lock = self.lock_writes()?;
+        match self.find_by_id(page.id())? {
+            Some(current) if current.content() == expected => {}
+            _ => return Ok(false),
+        }
+        self.write_page_file(&page, true)?;
+        self.save_cache(page)?;
+        Ok(true)
+    }
+
+    fn trash(&self, page_id: &PageId) -> anyhow::Result<bool> {
+        let _write_lock = self.lock_writes()?;
+        let file_name = self.data_dir.join(to_file_name(page_id));
+        if !file_name.exists() {
+            return Ok(false);
+        }
+        let trash_dir = self.trash_dir();
+        fs::create_dir_all(trash_dir.as_path())?;
+        fs::rename(file_name, trash_dir.join(to_file_name(page_id)))?;
+        self.destroy_cache(page_id)?;
+        Ok(true)
     }
 }
 
@@ -125,6 +453,85 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn create_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageRepository::new(data_dir);
+
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        let page1 = Page::new(page_id, PageContent::from("content1".to_string()));
+        let page2 = Page::new(page_id, PageContent::from("content2".to_string()));
+        assert!(repository.create(page1.clone())?);
+        assert!(!repository.create(page2)?);
+        assert_eq!(repository.find_by_id(&page_id)?, Some(page1));
+
+        Ok(())
+    }
+
+    #[test]
+    fn create_if_not_obsoleted_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageRepository::new(data_dir);
+
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let page_id3 = PageId::from_str("20210203T040508Z")?;
+        let obsoletes = |page_id: &PageId| {
+            let mut page_content = PageContent::from("# title".to_string());
+            page_content.replace_obsoletes(*page_id);
+            page_content
+        };
+        repository.create(Page::new(
+            page_id1,
+            PageContent::from("# title".to_string()),
+        ))?;
+        assert!(repository
+            .create_if_not_obsoleted(Page::new(page_id2, obsoletes(&page_id1)), &page_id1)?);
+        // the second edit of the same version
+        let e = repository
+            .create_if_not_obsoleted(Page::new(page_id3, obsoletes(&page_id1)), &page_id1)
+            .unwrap_err();
+        assert!(e.is::<PageObsoletedError>());
+        assert!(repository.find_by_id(&page_id3)?.is_none());
+        // the id is used
+        assert!(!repository
+            .create_if_not_obsoleted(Page::new(page_id2, obsoletes(&page_id2)), &page_id2)?);
+        Ok(())
+    }
+
+    #[test]
+    fn create_if_untitled_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageRepository::new(data_dir);
+
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let page_id3 = PageId::from_str("20210203T040508Z")?;
+        let page_content = PageContent::from("# title".to_string());
+        assert!(repository.create_if_untitled(Page::new(page_id1, page_content.clone()))?);
+        // the second request for the same title
+        let e = repository
+            .create_if_untitled(Page::new(page_id2, page_content.clone()))
+            .unwrap_err();
+        assert!(matches!(
+            e.downcast_ref::<PageTitledError>(),
+            Some(PageTitledError(page_id)) if page_id == &page_id1
+        ));
+        assert!(repository.find_by_id(&page_id2)?.is_none());
+        // the obsoleted page doesn't have the title
+        let mut obsoleting = PageContent::from("# other".to_string());
+        obsoleting.replace_obsoletes(page_id1);
+        repository.create(Page::new(page_id2, obsoleting))?;
+        assert!(repository.create_if_untitled(Page::new(page_id3, page_content.clone()))?);
+        // the id is used
+        let other = PageContent::from("# other title".to_string());
+        assert!(!repository.create_if_untitled(Page::new(page_id3, other))?);
+        Ok(())
+    }
+
     #[test]
     fn destroy_test() -> anyhow::Result<()> {
         let temp_dir = tempdir()?;
@@ -160,6 +567,87 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn find_by_query_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageRepository::new(data_dir.clone());
+
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        repository.save(Page::new(
+            page_id1,
+            PageContent::from("# foo\n\nあいう".to_string()),
+        ))?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        repository.save(Page::new(page_id2, PageContent::from("# bar".to_string())))?;
+        let ids = |query: &str| -> anyhow::Result<Vec<PageId>> {
+            Ok(repository
+                .find_by_query(&Query::from_str(query)?)?
+                .into_iter()
+                .map(|(page_id, _)| page_id)
+                .collect())
+        };
+        assert_eq!(ids("foo")?, vec![page_id1]);
+        assert_eq!(ids("いう")?, vec![page_id1]);
+        assert_eq!(ids("-foo")?, vec![page_id2]);
+        assert!(index_path(data_dir.as_path()).exists());
+
+        // modified outside of the repository
+        fs::write(data_dir.join("20210203T040507Z.md"), "# bar baz")?;
+        assert_eq!(ids("baz")?, vec![page_id2]);
+        fs::remove_file(data_dir.join("20210203T040506Z.md"))?;
+        assert_eq!(ids("foo")?, vec![]);
+
+        repository.destroy(&page_id2)?;
+        assert_eq!(ids("bar")?, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn load_page_graph_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        fs::write(data_dir.join("20210203T040506Z.md"), "# foo")?;
+
+        // cold: loaded from the files
+        let repository = FsPageRepository::new(data_dir.clone());
+        assert!(repository.is_cache_consistent()?);
+        let page_graph = repository.load_page_graph()?;
+        assert!(page_graph
+            .titled(&PageTitle::from("foo".to_string()))
+            .contains(&page_id1));
+
+        // shared, not copied
+        assert!(Arc::ptr_eq(&page_graph, &repository.load_page_graph()?));
+
+        // warm: updated through the repository (copied on write)
+        repository.save(Page::new(page_id2, PageContent::from("# bar".to_string())))?;
+        assert!(page_graph.title(&page_id2).is_none());
+        let page_graph = repository.load_page_graph()?;
+        assert!(page_graph
+            .titled(&PageTitle::from("bar".to_string()))
+            .contains(&page_id2));
+        assert!(repository.is_cache_consistent()?);
+        repository.destroy(&page_id2)?;
+        assert!(repository.load_page_graph()?.title(&page_id2).is_none());
+        assert!(repository.is_cache_consistent()?);
+
+        // modified outside of the repository
+        fs::write(data_dir.join("20210203T040506Z.md"), "# baz")?;
+        assert!(!repository.is_cache_consistent()?);
+        let page_graph = repository.load_page_graph()?;
+        assert!(page_graph
+            .titled(&PageTitle::from("foo".to_string()))
+            .contains(&page_id1));
+        repository.save_cache(Page::new(page_id1, PageContent::from("# baz".to_string())))?;
+        assert!(repository.is_cache_consistent()?);
+
+        Ok(())
+    }
+
     #[test]
     fn find_ids_test() -> anyhow::Result<()> {
         let temp_dir = tempdir()?;
@@ -183,7 +671,7 @@ mod tests {
     fn save_test() -> anyhow::Result<()> {
         let temp_dir = tempdir()?;
         let data_dir = temp_dir.path().to_path_buf();
-        let repository = FsPageRepository::new(data_dir);
+        let repository = FsPageRepository::new(data_dir.clone());
 
         let page_id = PageId::from_str("20210203T040506Z")?;
         assert!(repository.find_by_id(&page_id)?.is_none());
@@ -194,6 +682,107 @@ mod tests {
             Some(Page::new(page_id, page_content))
         );
 
+        // overwrites the file and leaves no temp file
+        let page_content = PageContent::from("updated".to_string());
+        repository.save(Page::new(page_id, page_content.clone()))?;
+        assert_eq!(
+            repository.find_by_id(&page_id)?,
+            Some(Page::new(page_id, page_content))
+        );
+        let file_names = fs::read_dir(data_dir.as_path())?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().to_string()))
+            .collect::<anyhow::Result<BTreeSet<String>>>()?;
+        assert_eq!(
+            file_names,
+            vec![".rust-memo", "20210203T040506Z.md"]
+                .into_iter()
+                .map(String::from)
+                .collect::<BTreeSet<String>>()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn save_if_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageRepository::new(data_dir);
+
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        let page_content1 = PageContent::from("content1".to_string());
+        let page_content2 = PageContent::from("content2".to_string());
+        let page_content3 = PageContent::from("content3".to_string());
+        // not found
+        assert!(!repository.save_if(Page::new(page_id, page_content2.clone()), &page_content1)?);
+        assert!(repository.find_by_id(&page_id)?.is_none());
+
+        repository.save(Page::new(page_id, page_content1.clone()))?;
+        assert!(repository.save_if(Page::new(page_id, page_content2.clone()), &page_content1)?);
+        // the second update based on the same content is rejected
+        assert!(!repository.save_if(Page::new(page_id, page_content3), &page_content1)?);
+        assert_eq!(
+            repository.find_by_id(&page_id)?,
+            Some(Page::new(page_id, page_content2))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn trash_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageRepository::new(data_dir);
+
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        let page = Page::new(page_id, PageContent::from("# foo".to_string()));
+        assert!(!repository.trash(&page_id)?);
+        repository.save(page.clone())?;
+        let foo = PageTitle::from("foo".to_string());
+        assert!(!repository.load_page_graph()?.titled(&foo).is_empty());
+
+        assert!(repository.trash(&page_id)?);
+        assert_eq!(repository.find_by_id(&page_id)?, None);
+        assert_eq!(repository.find_ids()?, vec![]);
+        assert_eq!(repository.find_trashed_ids()?, vec![page_id]);
+        assert_eq!(repository.find_trashed_by_id(&page_id)?, Some(page.clone()));
+        assert!(repository.load_page_graph()?.titled(&foo).is_empty());
+
+        assert!(repository.restore(&page_id)?);
+        assert_eq!(repository.find_by_id(&page_id)?, Some(page.clone()));
+        assert_eq!(repository.find_trashed_ids()?, vec![]);
+        assert!(!repository.load_page_graph()?.titled(&foo).is_empty());
+        assert!(!repository.restore(&page_id)?);
+
+        repository.trash(&page_id)?;
+        let recreated = Page::new(page_id, PageContent::from("# bar".to_string()));
+        repository.save(recreated.clone())?;
+        assert!(repository.restore(&page_id).is_err());
+        // not overwritten
+        assert_eq!(repository.find_by_id(&page_id)?, Some(recreated));
+        assert_eq!(repository.find_trashed_by_id(&page_id)?, Some(page));
+        assert!(repository.purge(&page_id)?);
+        assert!(!repository.purge(&page_id)?);
+        assert_eq!(repository.find_trashed_ids()?, vec![]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reindex_test() -> anyhow::Result<()> {
+        let temp_dir = tempdir()?;
+        let data_dir = temp_dir.path().to_path_buf();
+        let repository = FsPageRepository::new(data_dir.clone());
+
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        repository.save(Page::new(page_id, PageContent::from("foo".to_string())))?;
+        fs::remove_file(index_path(data_dir.as_path()))?;
+
+        let repository = FsPageRepository::new(data_dir.clone());
+        repository.reindex()?;
+        assert!(index_path(data_dir.as_path()).exists());
+        assert_eq!(repository.find_by_query(&Query::from_str("foo")?)?.len(), 1);
+
         Ok(())
     }
 }
@@ -1,19 +1,36 @@
 use std::path::PathBuf;
 
-use adapter_fs::FsPageRepository;
+use adapter_fs::{FsPageContentTemplateRepository, FsPageRepository};
 use use_case::{
-    HasEditPageUseCase, HasEnsureLinksUseCase, HasListPagesUseCase, HasListTitlesUseCase,
-    HasNewPageUseCase, HasPageRepository,
+    HasCheckLinksUseCase, HasConvertLinksUseCase, HasCreatePageUseCase, HasDailyPageUseCase,
+    HasDeletePageUseCase, HasEditPageUseCase, HasEnsureLinksUseCase, HasListPagesUseCase,
+    HasListTitlesUseCase, HasNewPageFromTemplateUseCase, HasNewPageUseCase,
+    HasPageContentTemplateRepository, HasPageRepository, HasRenameTitleUseCase,
+    HasSearchPagesUseCase, HasUnlinkedMentionsUseCase,
 };
 
 pub struct App {
+    fs_page_content_template_repository: FsPageContentTemplateRepository,
     fs_repository: FsPageRepository,
 }
 
 impl App {
     pub fn new(data_dir: PathBuf) -> Self {
+        let fs_page_content_template_repository =
+            FsPageContentTemplateRepository::new(data_dir.clone());
         let fs_repository = FsPageRepository::new(data_dir);
-        Self { fs_repository }
+        Self {
+            fs_page_content_template_repository,
+            fs_repository,
+        }
+    }
+}
+
+impl HasPageContentTemplateRepository for App {
+    type PageContentTemplateRepository = FsPageContentTemplateRepository;
+
+    fn page_content_template_repository(&self) -> &Self::PageContentTemplateRepository {
+        &self.fs_page_content_template_repository
     }
 }
 
@@ -25,6 +42,46 @@ impl HasPageRepository for App {
     }
 }
 
+impl HasCheckLinksUseCase for App {
+    type CheckLinksUseCase = App;
+
+    fn check_links_use_case(&self) -> &Self::CheckLinksUseCase {
+        self
+    }
+}
+
+impl HasConvertLinksUseCase for App {
+    type ConvertLinksUseCase = App;
+
+    fn convert_links_use_case(&self) -> &Self::ConvertLinksUseCase {
+        self
+    }
+}
+
+impl HasCreatePageUseCase for App {
+    type CreatePageUseCase = App;
+
+    fn create_page_use_case(&self) -> &Self::CreatePageUseCase {
+        self
+    }
+}
+
+impl HasDailyPageUseCase for App {
+    type DailyPageUseCase = App;
+
+    fn daily_page_use_case(&self) -> &Self::DailyPageUseCase {
+        self
+    }
+}
+
+impl HasDeletePageUseCase for App {
+    type DeletePageUseCase = App;
+
+    fn delete_page_use_case(&self) -> &Self::DeletePageUseCase {
+        self
+    }
+}
+
 impl HasEditPageUseCase for App {
     type EditPageUseCase = App;
 
@@ -57,6 +114,14 @@ impl HasListTitlesUseCase for App {
     }
 }
 
+impl HasNewPageFromTemplateUseCase for App {
+    type NewPageFromTemplateUseCase = App;
+
+    fn new_page_from_template_use_case(&self) -> &Self::NewPageFromTemplateUseCase {
+        self
+    }
+}
+
 impl HasNewPageUseCase for App {
     type NewPageUseCase = App;
 
@@ -64,3 +129,27 @@ impl HasNewPageUseCase for App {
         self
     }
 }
+
+impl HasRenameTitleUseCase for App {
+    type RenameTitleUseCase = App;
+
+    fn rename_title_use_case(&self) -> &Self::RenameTitleUseCase {
+        self
+    }
+}
+
+impl HasSearchPagesUseCase for App {
+    type SearchPagesUseCase = App;
+
+    fn search_pages_use_case(&self) -> &Self::SearchPagesUseCase {
+        self
+    }
+}
+
+impl HasUnlinkedMentionsUseCase for App {
+    type UnlinkedMentionsUseCase = App;
+
+    fn unlinked_mentions_use_case(&self) -> &Self::UnlinkedMentionsUseCase {
+        self
+    }
+}
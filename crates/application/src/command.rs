@@ -1,20 +1,36 @@
+mod check_links;
+mod convert_links;
 mod create;
+mod daily;
+mod delete;
 mod edit;
 mod ensure_links;
 mod link;
 mod list;
 mod list_title;
+mod mentions;
+mod reindex;
+mod rename_title;
 mod search;
 mod server;
 mod title;
+mod trash;
 
+pub use self::check_links::*;
+pub use self::convert_links::*;
 pub use self::create::*;
+pub use self::daily::*;
+pub use self::delete::*;
 pub use self::edit::*;
 pub use self::ensure_links::*;
 pub use self::link::*;
 pub use self::list::*;
 pub use self::list_title::*;
+pub use self::mentions::*;
+pub use self::reindex::*;
+pub use self::rename_title::*;
 pub use self::search::*;
 pub use self::server::*;
 pub use self::title::*;
 pub use self::title::*;
+pub use self::trash::*;
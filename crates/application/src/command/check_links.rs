@@ -0,0 +1,39 @@
+use anyhow::anyhow;
+use use_case::{CheckLinksUseCase, HasCheckLinksUseCase};
+
+use crate::helpers::to_file_name;
+
+pub fn check_links<App: HasCheckLinksUseCase>(
+    app: App,
+    obsoleted: bool,
+    ci: bool,
+) -> anyhow::Result<()> {
+    let wanted_titles = app.check_links_use_case().find_wanted_titles(obsoleted)?;
+    for (page_title, page_ids) in wanted_titles.iter() {
+        println!("wanted: {} ({})", page_title, page_ids.len());
+        for page_id in page_ids {
+            println!("\t{}", to_file_name(page_id));
+        }
+    }
+    let obsoleted_page_links = app
+        .check_links_use_case()
+        .find_obsoleted_page_links(obsoleted)?;
+    for (page_id, linked_page_ids) in obsoleted_page_links.iter() {
+        for linked_page_id in linked_page_ids {
+            println!(
+                "obsoleted: {} -> {}",
+                to_file_name(page_id),
+                to_file_name(linked_page_id)
+            );
+        }
+    }
+    let count = wanted_titles.len() + obsoleted_page_links.len();
+    if ci && count > 0 {
+        return Err(anyhow!(
+            "{} wanted titles and {} pages with links to obsoleted versions",
+            wanted_titles.len(),
+            obsoleted_page_links.len()
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use entity::{PageId, PageLinkStyle};
+use use_case::{ConvertLinksUseCase, HasConvertLinksUseCase};
+
+use crate::helpers::to_file_name;
+
+pub fn convert_links<App: HasConvertLinksUseCase>(
+    app: App,
+    id_like: Option<String>,
+    page_link_style: PageLinkStyle,
+) -> anyhow::Result<()> {
+    let page_id = id_like
+        .map(|id_like| PageId::from_like_str(id_like.as_str()))
+        .transpose()?;
+    let page_ids = app
+        .convert_links_use_case()
+        .convert_links(page_id.as_ref(), page_link_style)?;
+    for page_id in page_ids {
+        println!("{}", to_file_name(&page_id));
+    }
+    Ok(())
+}
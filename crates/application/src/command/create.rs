@@ -1,23 +1,50 @@
 use std::collections::HashMap;
 
-use use_case::HasNewPageUseCase;
+use entity::PageTitle;
+use use_case::{
+    HasNewPageFromTemplateUseCase, HasNewPageUseCase, HasPageRepository,
+    NewPageFromTemplateUseCase, NewPageUseCase,
+};
 
-#[derive(serde::Serialize)]
-struct Body {
-    content: String,
+use crate::{editor::open_new_page, helpers::to_file_name};
+
+pub fn create<App: HasNewPageFromTemplateUseCase + HasNewPageUseCase + HasPageRepository>(
+    app: App,
+    title: Option<&str>,
+    template: Option<&str>,
+    server: Option<&str>,
+    open: bool,
+) -> anyhow::Result<()> {
+    if let Some(server) = server {
+        return create_via_server(server, title);
+    }
+    let page_title = PageTitle::from(title.unwrap_or_default().to_string());
+    let (page_id, already_exists) = match template {
+        Some(template) => app
+            .new_page_from_template_use_case()
+            .new_page_from_template(page_title, template)?,
+        None => app.new_page_use_case().new_page(page_title)?,
+    };
+    println!("{}", to_file_name(&page_id));
+    if let Some(title) = title.filter(|_| already_exists) {
+        eprintln!("warning: the title already exists : {}", title);
+    }
+    if open && !open_new_page(&app, &page_id)? {
+        println!("{} is deleted (unchanged)", to_file_name(&page_id));
+    }
+    Ok(())
 }
 
-pub fn create<App: HasNewPageUseCase>(_app: App, title: Option<&str>) -> anyhow::Result<()> {
+// lets the server save the memo so that its file watcher picks it up
+fn create_via_server(server: &str, title: Option<&str>) -> anyhow::Result<()> {
+    let url = url::Url::parse(server)?.join("/pages")?;
     let content = title.map(|s| format!("# {}", s)).unwrap_or_default();
     let client = reqwest::blocking::Client::builder()
         .redirect(reqwest::redirect::Policy::none())
         .build()?;
     let mut form = HashMap::new();
     form.insert("content", content);
-    let response = client
-        .post("http://localhost:3000/pages")
-        .form(&form)
-        .send()?;
+    let response = client.post(url).form(&form).send()?;
     let created = if response.status().is_redirection() {
         if let Some(location) = response.headers().get(reqwest::header::LOCATION) {
             match location.to_str() {
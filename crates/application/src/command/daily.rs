@@ -0,0 +1,17 @@
+use anyhow::{anyhow, Context};
+use chrono::{Local, NaiveDate};
+use use_case::{DailyPageUseCase, HasDailyPageUseCase};
+
+use crate::{config::daily_title_format, helpers::to_file_name};
+
+pub fn daily<App: HasDailyPageUseCase>(app: App, date: Option<&str>) -> anyhow::Result<()> {
+    let date = match date {
+        Some(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .with_context(|| anyhow!("invalid date (YYYY-MM-DD): {}", s))?,
+        None => Local::today().naive_local(),
+    };
+    let format = daily_title_format()?;
+    let (page_id, _) = app.daily_page_use_case().daily_page(date, &format)?;
+    println!("{}", to_file_name(&page_id));
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use entity::PageId;
+use use_case::{DeletePageUseCase, HasDeletePageUseCase};
+
+use crate::helpers::to_file_name;
+
+pub fn delete<App: HasDeletePageUseCase>(app: App, id_like: &str) -> anyhow::Result<()> {
+    let page_id = PageId::from_like_str(id_like)?;
+    let (linked_by, obsoleted_by) = app.delete_page_use_case().delete_page(&page_id)?;
+    println!("{}", to_file_name(&page_id));
+    for linker_id in linked_by {
+        eprintln!(
+            "warning: {} links to the deleted memo",
+            to_file_name(&linker_id)
+        );
+    }
+    for obsoleter_id in obsoleted_by {
+        eprintln!(
+            "warning: {} obsoletes the deleted memo",
+            to_file_name(&obsoleter_id)
+        );
+    }
+    Ok(())
+}
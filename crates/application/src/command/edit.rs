@@ -1,9 +1,13 @@
 use entity::PageIdOrPageTitle;
-use use_case::{EditPageUseCase, HasEditPageUseCase};
+use use_case::{EditPageUseCase, HasEditPageUseCase, HasPageRepository};
 
-use crate::helpers::to_file_name;
+use crate::{editor::open_new_page, helpers::to_file_name};
 
-pub fn edit<T: HasEditPageUseCase>(app: T, id_like_or_title: &str) -> anyhow::Result<()> {
+pub fn edit<T: HasEditPageUseCase + HasPageRepository>(
+    app: T,
+    id_like_or_title: &str,
+    open: bool,
+) -> anyhow::Result<()> {
     let page_id_or_page_title = PageIdOrPageTitle::from(id_like_or_title);
     let (old_page_id, new_page_id, is_obsoleted) =
         app.edit_page_use_case().edit_page(&page_id_or_page_title)?;
@@ -14,5 +18,9 @@ pub fn edit<T: HasEditPageUseCase>(app: T, id_like_or_title: &str) -> anyhow::Re
     if is_obsoleted {
         println!("{} is obsoleted", old_page_id);
     }
+    // the old page is left untouched, so removing the new page undoes the edit
+    if open && !open_new_page(&app, &new_page_id)? {
+        println!("{} is deleted (unchanged)", new_file_name);
+    }
     Ok(())
 }
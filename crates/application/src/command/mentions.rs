@@ -0,0 +1,47 @@
+use entity::{PageId, PageTitle};
+use use_case::{
+    HasPageRepository, HasUnlinkedMentionsUseCase, PageRepository, UnlinkedMentionsUseCase,
+};
+
+use crate::helpers::to_file_name;
+
+pub fn mentions<App: HasPageRepository + HasUnlinkedMentionsUseCase>(
+    app: App,
+    title: &str,
+    link: Option<String>,
+) -> anyhow::Result<()> {
+    let page_title = PageTitle::from(title.to_string());
+    if let Some(id_like) = link {
+        let page_id = PageId::from_like_str(id_like.as_str())?;
+        let new_page_id = app
+            .unlinked_mentions_use_case()
+            .link_mentions(&page_id, &page_title)?;
+        println!(
+            "{} -> {}",
+            to_file_name(&page_id),
+            to_file_name(&new_page_id)
+        );
+        return Ok(());
+    }
+    for (page_id, spans) in app
+        .unlinked_mentions_use_case()
+        .find_unlinked_mentions(&page_title)?
+    {
+        let page = match app.page_repository().find_by_id(&page_id)? {
+            Some(page) => page,
+            None => continue,
+        };
+        let lines = page.content().as_str().lines().collect::<Vec<&str>>();
+        for span in spans {
+            let line_number = usize::from(span.line_number());
+            println!(
+                "{}:{}:{}\t{}",
+                to_file_name(&page_id),
+                line_number,
+                span.column_number(),
+                lines.get(line_number - 1).unwrap_or(&"")
+            );
+        }
+    }
+    Ok(())
+}
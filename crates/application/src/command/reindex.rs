@@ -0,0 +1,5 @@
+use use_case::{HasPageRepository, PageRepository};
+
+pub fn reindex<App: HasPageRepository>(app: App) -> anyhow::Result<()> {
+    app.page_repository().reindex()
+}
@@ -0,0 +1,70 @@
+use entity::{PageContent, PageTitle};
+use use_case::{HasRenameTitleUseCase, RenameTitleUseCase};
+
+use crate::helpers::to_file_name;
+
+// the renames replace the text in the line and `## Obsoletes` is at the end,
+// so the lines are compared one by one
+fn print_diff(old_file_name: &str, old: &PageContent, new: &PageContent) {
+    println!("--- {}", old_file_name);
+    println!("+++ {}", old_file_name);
+    let old_lines = old.as_str().lines().collect::<Vec<&str>>();
+    let new_lines = new.as_str().lines().collect::<Vec<&str>>();
+    let len = old_lines.len().max(new_lines.len());
+    let mut i = 0;
+    while i < len {
+        if old_lines.get(i) == new_lines.get(i) {
+            i += 1;
+            continue;
+        }
+        // prints the consecutive changed lines as a hunk
+        let start = i;
+        while i < len && old_lines.get(i) != new_lines.get(i) {
+            i += 1;
+        }
+        let old_hunk = &old_lines[start.min(old_lines.len())..i.min(old_lines.len())];
+        let new_hunk = &new_lines[start.min(new_lines.len())..i.min(new_lines.len())];
+        // an empty hunk starts at the line before it (unified diff format)
+        let hunk_start = |hunk: &[&str]| if hunk.is_empty() { start } else { start + 1 };
+        println!(
+            "@@ -{},{} +{},{} @@",
+            hunk_start(old_hunk),
+            old_hunk.len(),
+            hunk_start(new_hunk),
+            new_hunk.len()
+        );
+        for old_line in old_hunk {
+            println!("-{}", old_line);
+        }
+        for new_line in new_hunk {
+            println!("+{}", new_line);
+        }
+    }
+}
+
+pub fn rename_title<App: HasRenameTitleUseCase>(
+    app: App,
+    from: &str,
+    to: &str,
+    dry_run: bool,
+) -> anyhow::Result<()> {
+    let from = PageTitle::from(from.to_string());
+    let to = PageTitle::from(to.to_string());
+    if dry_run {
+        let renames = app.rename_title_use_case().find_title_renames(&from, &to)?;
+        for (page, mut page_content) in renames {
+            // same as the content of the new page
+            page_content.replace_obsoletes(*page.id());
+            print_diff(&to_file_name(page.id()), page.content(), &page_content);
+        }
+        return Ok(());
+    }
+    for (old_page_id, new_page_id) in app.rename_title_use_case().rename_title(&from, &to)? {
+        println!(
+            "{} -> {}",
+            to_file_name(&old_page_id),
+            to_file_name(&new_page_id)
+        );
+    }
+    Ok(())
+}
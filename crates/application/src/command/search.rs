@@ -1,16 +1,110 @@
-use entity::Query;
-use use_case::{HasPageRepository, PageRepository};
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{anyhow, Context};
+use entity::{PageId, Query, QueryMatch, QueryOptions, Snippet};
+use use_case::{HasPageRepository, HasSearchPagesUseCase, PageRepository, SearchPagesUseCase};
 
 use crate::helpers::to_file_name;
 
-pub fn search<App: HasPageRepository>(app: App, query: Query, all: bool) -> anyhow::Result<()> {
+fn highlight(snippet: &Snippet) -> String {
+    snippet
+        .fragments()
+        .into_iter()
+        .map(|(text, highlighted)| {
+            if highlighted {
+                format!("**{}**", text)
+            } else {
+                text.to_string()
+            }
+        })
+        .collect::<String>()
+}
+
+pub fn search<App: HasPageRepository + HasSearchPagesUseCase>(
+    app: App,
+    query: &str,
+    options: QueryOptions,
+    all: bool,
+    context: Option<usize>,
+    rank: bool,
+) -> anyhow::Result<()> {
+    let query = Query::parse(query, options)?;
+    if rank {
+        let hits = app.search_pages_use_case().search_pages(&query, all)?;
+        for hit in hits {
+            if let Some(snippet) = hit.snippet() {
+                println!(
+                    "{}:{}\t{}\t{}",
+                    to_file_name(hit.page_id()),
+                    snippet.line_number(),
+                    hit.title(),
+                    highlight(snippet)
+                );
+            }
+        }
+        return Ok(());
+    }
+
     let page_graph = app.page_repository().load_page_graph()?;
     let matches = app.page_repository().find_by_query(&query)?;
-    for (page_id, line, col) in matches {
+    let mut grouped: BTreeMap<PageId, Vec<QueryMatch>> = BTreeMap::new();
+    for (page_id, query_match) in matches {
         if !all && page_graph.is_obsoleted(&page_id) {
             continue;
         }
-        println!("{}:{}:{}", to_file_name(&page_id), line, col);
+        match context {
+            None => println!(
+                "{}:{}:{}",
+                to_file_name(&page_id),
+                query_match.line_number(),
+                query_match.column_number()
+            ),
+            Some(_) => grouped.entry(page_id).or_default().push(query_match),
+        }
+    }
+
+    if let Some(context) = context {
+        let mut first = true;
+        for (page_id, matches) in grouped {
+            let page = app
+                .page_repository()
+                .find_by_id(&page_id)?
+                .with_context(|| anyhow!("file not found: {}", page_id))?;
+            let lines = page.content().as_str().lines().collect::<Vec<&str>>();
+            let matched_lines = matches
+                .iter()
+                .map(|m| usize::from(m.line_number()) - 1)
+                .collect::<BTreeSet<usize>>();
+            let last_line = lines.len().max(1) - 1;
+            let printed_lines = matched_lines
+                .iter()
+                .flat_map(|l| l.saturating_sub(context)..=(l + context).min(last_line))
+                .collect::<BTreeSet<usize>>();
+            let mut prev = None;
+            for l in printed_lines {
+                if !first && prev.map(|p| p + 1 != l).unwrap_or(true) {
+                    println!("--");
+                }
+                first = false;
+                prev = Some(l);
+                if matched_lines.contains(&l) {
+                    for m in matches
+                        .iter()
+                        .filter(|m| usize::from(m.line_number()) == l + 1)
+                    {
+                        println!(
+                            "{}:{}:{}:{}",
+                            to_file_name(&page_id),
+                            m.line_number(),
+                            m.column_number(),
+                            lines.get(l).unwrap_or(&"")
+                        );
+                    }
+                } else {
+                    println!("{}-{}-{}", to_file_name(&page_id), l + 1, lines[l]);
+                }
+            }
+        }
     }
     Ok(())
 }
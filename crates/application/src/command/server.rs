@@ -1,17 +1,27 @@
 mod handler;
 
 use std::{
-    sync::{Arc, Mutex},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
     thread,
 };
 
 use self::handler::{
-    index, page_create, page_list, page_update, page_view, title_list, title_page_list, title_view,
+    api_page_backlinks, api_page_list, api_page_obsoletion_chain, api_page_view, api_search,
+    api_title_list, api_title_view, daily, index, page_create, page_delete, page_edit,
+    page_edit_form, page_link_mentions, page_list, page_new, page_new_form, page_update, page_view,
+    preview, search, title_list, title_page_list, title_view, wanted,
 };
 use actix_web::web;
 use anyhow::Context as _;
-use entity::{Page, PageContent, PageId};
-use use_case::{HasListPagesUseCase, HasListTitlesUseCase, HasPageRepository, PageRepository};
+use entity::PageId;
+use use_case::{
+    HasCheckLinksUseCase, HasCreatePageUseCase, HasDailyPageUseCase, HasDeletePageUseCase,
+    HasEditPageUseCase, HasListPagesUseCase, HasListTitlesUseCase, HasNewPageFromTemplateUseCase,
+    HasNewPageUseCase, HasPageContentTemplateRepository, HasPageRepository, HasSearchPagesUseCase,
+    HasUnlinkedMentionsUseCase, PageRepository,
+};
 use watchexec::{
     config::{Config, ConfigBuilder},
     error::Result,
@@ -20,18 +30,30 @@ use watchexec::{
 };
 
 pub async fn server<
-    T: HasListTitlesUseCase + HasListPagesUseCase + HasPageRepository + Send + Sync + 'static,
+    T: HasCheckLinksUseCase
+        + HasCreatePageUseCase
+        + HasDailyPageUseCase
+        + HasDeletePageUseCase
+        + HasEditPageUseCase
+        + HasNewPageFromTemplateUseCase
+        + HasNewPageUseCase
+        + HasPageContentTemplateRepository
+        + HasListTitlesUseCase
+        + HasListPagesUseCase
+        + HasPageRepository
+        + HasSearchPagesUseCase
+        + HasUnlinkedMentionsUseCase
+        + Send
+        + Sync
+        + 'static,
 >(
     app: T,
 ) -> anyhow::Result<()> {
-    let page_repository = app.page_repository();
-    for page_id in page_repository.find_ids()? {
-        if let Some(page) = page_repository.find_by_id(&page_id)? {
-            page_repository.save_cache(page)?;
-        }
-    }
+    // warm the cache, the file watcher keeps it up to date
+    app.page_repository().load_page_graph()?;
 
-    let app = Arc::new(Mutex::new(app));
+    // the repository synchronizes its caches, so requests don't block each other
+    let app = Arc::new(app);
 
     // run file watcher
     let app1 = app.clone();
@@ -41,7 +63,9 @@ pub async fn server<
             .cmd(vec![":".into()])
             .build()
             .context("fail")?;
-        let handler = MyHandler::<T>(ExecHandler::new(config)?, app1);
+        // the watcher reports canonicalized paths
+        let data_dir = std::env::current_dir()?.canonicalize()?;
+        let handler = MyHandler::<T>(ExecHandler::new(config)?, app1, data_dir);
         watch(&handler).context("fail")?;
         Ok(())
     });
@@ -52,14 +76,43 @@ pub async fn server<
     let mut server = actix_web::HttpServer::new(move || {
         actix_web::App::new()
             .app_data(data.clone())
+            .service(
+                web::scope("/api/v1")
+                    .route("/pages", web::get().to(api_page_list::<T>))
+                    .route("/pages/{id}", web::get().to(api_page_view::<T>))
+                    .route(
+                        "/pages/{id}/backlinks",
+                        web::get().to(api_page_backlinks::<T>),
+                    )
+                    .route(
+                        "/pages/{id}/obsoletion-chain",
+                        web::get().to(api_page_obsoletion_chain::<T>),
+                    )
+                    .route("/search", web::get().to(api_search::<T>))
+                    .route("/titles", web::get().to(api_title_list::<T>))
+                    .route("/titles/{title}", web::get().to(api_title_view::<T>)),
+            )
             .route("/", web::get().to(index))
+            .route("/daily", web::get().to(daily::<T>))
             .route("/pages", web::get().to(page_list::<T>))
             .route("/pages", web::post().to(page_create::<T>))
+            .route("/pages/new", web::get().to(page_new_form))
+            .route("/pages/new", web::post().to(page_new::<T>))
             .route("/pages/{id}", web::get().to(page_view::<T>))
             .route("/pages/{id}", web::patch().to(page_update::<T>))
+            .route("/pages/{id}", web::delete().to(page_delete::<T>))
+            .route("/pages/{id}/edit", web::get().to(page_edit_form::<T>))
+            .route("/pages/{id}/edit", web::post().to(page_edit::<T>))
+            .route(
+                "/pages/{id}/link-mentions",
+                web::post().to(page_link_mentions::<T>),
+            )
+            .route("/preview", web::post().to(preview))
+            .route("/search", web::get().to(search::<T>))
             .route("/titles", web::get().to(title_list::<T>))
             .route("/titles/{title}", web::get().to(title_view::<T>))
             .route("/titles/{title}/pages", web::get().to(title_page_list::<T>))
+            .route("/wanted", web::get().to(wanted::<T>))
     });
     server = if let Some(l) = listenfd.take_tcp_listener(0)? {
         server.listen(l)?
@@ -73,7 +126,45 @@ pub async fn server<
     Ok(server.run().await?)
 }
 
-struct MyHandler<T>(ExecHandler, Arc<Mutex<T>>);
+struct MyHandler<T>(ExecHandler, Arc<T>, PathBuf);
+
+// the pages are only in the data dir (e.g. `.rust-memo/trash/{id}.md` is not a page)
+// ignores temp files (e.g. ".tmpXXXXXX") and other files
+fn page_id_from_path(data_dir: &Path, path: &Path) -> Option<PageId> {
+    if path.parent() != Some(data_dir) {
+        return None;
+    }
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .and_then(|s| s.strip_suffix(".md"))
+        .and_then(|s| PageId::from_str(s).ok())
+}
+
+impl<T: HasPageRepository> MyHandler<T> {
+    // reads the whole file, e.g. after a temp file is renamed into place
+    fn reload(&self, page_id: &PageId) -> Result<()> {
+        let app = &self.1;
+        let found = app
+            .page_repository()
+            .find_by_id(page_id)
+            .map_err(|_| watchexec::error::Error::Generic("failed to find by id".to_string()))?;
+        // removed again before the event is handled
+        if let Some(found) = found {
+            app.page_repository()
+                .save_cache(found)
+                .map_err(|_| watchexec::error::Error::Generic("failed to save".to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn unload(&self, page_id: &PageId) -> Result<()> {
+        let app = &self.1;
+        app.page_repository()
+            .destroy_cache(page_id)
+            .map_err(|_| watchexec::error::Error::Generic("failed to remove".to_string()))?;
+        Ok(())
+    }
+}
 
 impl<T: HasPageRepository + Send + Sync + 'static> Handler for MyHandler<T> {
     fn on_manual(&self) -> Result<bool> {
@@ -85,77 +176,32 @@ impl<T: HasPageRepository + Send + Sync + 'static> Handler for MyHandler<T> {
             match op.op {
                 None => continue,
                 Some(o) => {
-                    let page_id = if let Some(s) = op.path.as_os_str().to_str() {
-                        match PageId::from_like_str(s) {
-                            Err(_) => continue,
-                            Ok(page_id) => page_id,
-                        }
-                    } else {
-                        continue;
+                    let page_id = match page_id_from_path(&self.2, &op.path) {
+                        Some(page_id) => page_id,
+                        None => continue,
                     };
 
-                    let app = self.1.lock().map_err(|_| {
-                        watchexec::error::Error::Generic("failed to lock".to_string())
-                    })?;
-
                     if PathOp::is_rename(o) {
                         if !op.path.exists() {
                             println!("rename: {:?} (from)", page_id);
-                            app.page_repository().destroy_cache(&page_id).map_err(|_| {
-                                watchexec::error::Error::Generic("failed to destroy".to_string())
-                            })?;
+                            self.unload(&page_id)?;
                         } else {
                             println!("rename: {:?} (to)", page_id);
-                            let found = app
-                                .page_repository()
-                                .find_by_id(&page_id)
-                                .map_err(|_| {
-                                    watchexec::error::Error::Generic(
-                                        "failed to find by id".to_string(),
-                                    )
-                                })?
-                                .ok_or_else(|| {
-                                    watchexec::error::Error::Generic(
-                                        "failed to find by id".to_string(),
-                                    )
-                                })?;
-                            app.page_repository().save_cache(found).map_err(|_| {
-                                watchexec::error::Error::Generic("failed to save".to_string())
-                            })?;
+                            self.reload(&page_id)?;
                         }
                     } else {
                         if PathOp::is_create(o) {
                             println!("create: {:?}", page_id);
-                            app.page_repository()
-                                .save_cache(Page::new(page_id, PageContent::from("".to_string())))
-                                .map_err(|_| {
-                                    watchexec::error::Error::Generic("failed to save".to_string())
-                                })?;
+                            self.reload(&page_id)?;
                         }
-                        if PathOp::is_remove(o) {
+                        // a rename onto the file may be reported as remove
+                        if PathOp::is_remove(o) && !op.path.exists() {
                             println!("remove: {:?}", page_id);
-                            app.page_repository().destroy_cache(&page_id).map_err(|_| {
-                                watchexec::error::Error::Generic("failed to remove".to_string())
-                            })?;
+                            self.unload(&page_id)?;
                         }
                         if PathOp::is_write(o) {
                             println!("on write: {:?}", page_id);
-                            let found = app
-                                .page_repository()
-                                .find_by_id(&page_id)
-                                .map_err(|_| {
-                                    watchexec::error::Error::Generic(
-                                        "failed to find by id".to_string(),
-                                    )
-                                })?
-                                .ok_or_else(|| {
-                                    watchexec::error::Error::Generic(
-                                        "failed to find by id".to_string(),
-                                    )
-                                })?;
-                            app.page_repository().save_cache(found).map_err(|_| {
-                                watchexec::error::Error::Generic("failed to save".to_string())
-                            })?;
+                            self.reload(&page_id)?;
                         }
                         if PathOp::is_meta(o) {
                             println!("on meta: {:?}", page_id);
@@ -173,3 +219,35 @@ impl<T: HasPageRepository + Send + Sync + 'static> Handler for MyHandler<T> {
         self.0.args()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_id_from_path_test() -> anyhow::Result<()> {
+        let data_dir = Path::new("/memo");
+        assert_eq!(
+            page_id_from_path(data_dir, Path::new("/memo/20210203T040506Z.md")),
+            Some(PageId::from_str("20210203T040506Z")?)
+        );
+        // the trash
+        assert_eq!(
+            page_id_from_path(
+                data_dir,
+                Path::new("/memo/.rust-memo/trash/20210203T040506Z.md")
+            ),
+            None
+        );
+        assert_eq!(
+            page_id_from_path(data_dir, Path::new("/other/20210203T040506Z.md")),
+            None
+        );
+        assert_eq!(
+            page_id_from_path(data_dir, Path::new("/memo/.tmp123456")),
+            None
+        );
+        assert_eq!(page_id_from_path(data_dir, Path::new("/memo/foo.md")), None);
+        Ok(())
+    }
+}
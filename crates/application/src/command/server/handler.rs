@@ -1,18 +1,54 @@
+mod api_page_backlinks;
+mod api_page_list;
+mod api_page_obsoletion_chain;
+mod api_page_view;
+mod api_search;
+mod api_title_list;
+mod api_title_view;
+mod daily;
 pub mod helpers;
 mod index;
 mod page_create;
+mod page_delete;
+mod page_edit;
+mod page_edit_form;
+mod page_link_mentions;
 mod page_list;
+mod page_new;
+mod page_new_form;
 mod page_update;
 mod page_view;
+mod preview;
+mod search;
+mod server_error;
 mod title_list;
 mod title_page_list;
 mod title_view;
+mod wanted;
 
+pub use self::api_page_backlinks::*;
+pub use self::api_page_list::*;
+pub use self::api_page_obsoletion_chain::*;
+pub use self::api_page_view::*;
+pub use self::api_search::*;
+pub use self::api_title_list::*;
+pub use self::api_title_view::*;
+pub use self::daily::*;
 pub use self::index::*;
 pub use self::page_create::*;
+pub use self::page_delete::*;
+pub use self::page_edit::*;
+pub use self::page_edit_form::*;
+pub use self::page_link_mentions::*;
 pub use self::page_list::*;
+pub use self::page_new::*;
+pub use self::page_new_form::*;
 pub use self::page_update::*;
 pub use self::page_view::*;
+pub use self::preview::*;
+pub use self::search::*;
+pub use self::server_error::*;
 pub use self::title_list::*;
 pub use self::title_page_list::*;
 pub use self::title_view::*;
+pub use self::wanted::*;
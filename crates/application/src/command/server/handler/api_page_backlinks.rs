@@ -0,0 +1,41 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::helpers::is_all;
+use super::{ApiError, ServerError};
+use crate::json::PageWithTitleJson;
+use actix_web::{web, HttpResponse};
+use entity::{PageId, PagePath};
+use use_case::{HasPageRepository, PageRepository};
+
+pub async fn api_page_backlinks<T: HasPageRepository>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let all = is_all(&req);
+    let params: (String,) = req
+        .match_info()
+        .load()
+        .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let page_graph = app.page_repository().load_page_graph()?;
+    let page_id =
+        PageId::from_str(&params.0).map_err(|_| ServerError::InvalidPageId(params.0.clone()))?;
+    let title = page_graph
+        .title(&page_id)
+        .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+    let linked_by = page_graph
+        .find_ids_link_to(&title)
+        .into_iter()
+        .filter(|page_id| all || !page_graph.is_obsoleted(page_id))
+        .map(|page_id| PageWithTitleJson {
+            id: page_id.to_string(),
+            obsoleted: page_graph.is_obsoleted(&page_id),
+            title: page_graph
+                .title(&page_id)
+                .map(String::from)
+                .unwrap_or_default(),
+            url: PagePath::from(page_id).to_string(),
+        })
+        .collect::<Vec<PageWithTitleJson>>();
+    Ok(HttpResponse::Ok().json(linked_by))
+}
@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use super::helpers::is_all;
+use super::ApiError;
+use crate::json::PageItemJson;
+use actix_web::{web, HttpResponse};
+use entity::PagePath;
+use use_case::{HasListPagesUseCase, ListPagesUseCase};
+
+pub async fn api_page_list<T: HasListPagesUseCase>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let all = is_all(&req);
+    let pages = app.list_pages_use_case().list_pages(all)?;
+    let pages = pages
+        .into_iter()
+        .map(|(page_id, obsoleted)| PageItemJson {
+            id: page_id.to_string(),
+            obsoleted,
+            url: PagePath::from(page_id).to_string(),
+        })
+        .collect::<Vec<PageItemJson>>();
+    Ok(HttpResponse::Ok().json(pages))
+}
@@ -0,0 +1,35 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::{ApiError, ServerError};
+use crate::json::PageItemJson;
+use actix_web::{web, HttpResponse};
+use entity::{PageId, PagePath};
+use use_case::{HasPageRepository, PageRepository};
+
+pub async fn api_page_obsoletion_chain<T: HasPageRepository>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let params: (String,) = req
+        .match_info()
+        .load()
+        .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let page_graph = app.page_repository().load_page_graph()?;
+    let page_id =
+        PageId::from_str(&params.0).map_err(|_| ServerError::InvalidPageId(params.0.clone()))?;
+    if page_graph.title(&page_id).is_none() {
+        return Err(ServerError::PageNotFound(page_id.to_string()).into());
+    }
+    // oldest first
+    let chain = page_graph
+        .obsoletion_chain(&page_id)
+        .into_iter()
+        .map(|page_id| PageItemJson {
+            id: page_id.to_string(),
+            obsoleted: page_graph.is_obsoleted(&page_id),
+            url: PagePath::from(page_id).to_string(),
+        })
+        .collect::<Vec<PageItemJson>>();
+    Ok(HttpResponse::Ok().json(chain))
+}
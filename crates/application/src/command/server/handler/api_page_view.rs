@@ -0,0 +1,60 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::helpers::page_etag;
+use super::{ApiError, ServerError};
+use crate::json::{PageItemJson, PageJson};
+use actix_web::{http::header::ETag, web, HttpResponse};
+use entity::{PageId, PagePath, TitlePath};
+use use_case::{HasPageRepository, PageRepository};
+
+pub async fn api_page_view<T: HasPageRepository>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let params: (String,) = req
+        .match_info()
+        .load()
+        .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let page_id =
+        PageId::from_str(&params.0).map_err(|_| ServerError::InvalidPageId(params.0.clone()))?;
+    let page = app
+        .page_repository()
+        .find_by_id(&page_id)?
+        .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+    let page_graph = app.page_repository().load_page_graph()?;
+    let page_items = |page_ids: Vec<PageId>| {
+        page_ids
+            .into_iter()
+            .map(|page_id| PageItemJson {
+                id: page_id.to_string(),
+                obsoleted: page_graph.is_obsoleted(&page_id),
+                url: PagePath::from(page_id).to_string(),
+            })
+            .collect::<Vec<PageItemJson>>()
+    };
+    Ok(HttpResponse::Ok()
+        .set(ETag(page_etag(page.content())))
+        .json(PageJson {
+            content: page.content().as_str().to_string(),
+            id: page_id.to_string(),
+            obsoleted: page_graph.is_obsoleted(&page_id),
+            obsoleted_by: page_items(page_graph.obsoleted_by(&page_id).into_iter().collect()),
+            obsoleted_links: page_items(
+                page_graph
+                    .obsoleted_page_links(&page_id)
+                    .into_iter()
+                    .collect(),
+            ),
+            obsoletes: page_items(page_graph.obsoletes(&page_id).into_iter().collect()),
+            page_linked_by: page_items(
+                page_graph
+                    .find_ids_link_to_page(&page_id)
+                    .into_iter()
+                    .collect(),
+            ),
+            title: page.title().to_string(),
+            title_url: TitlePath::from(page.title()).to_string(),
+            url: PagePath::from(page_id).to_string(),
+        }))
+}
@@ -0,0 +1,49 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::helpers::{is_all, query_param};
+use super::{ApiError, ServerError};
+use crate::json::{SearchHitJson, SnippetFragmentJson, SnippetJson};
+use actix_web::{web, HttpResponse};
+use entity::{PagePath, Query};
+use use_case::{HasSearchPagesUseCase, SearchPagesUseCase};
+
+pub async fn api_search<T: HasSearchPagesUseCase>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let all = is_all(&req);
+    let q = query_param(&req, "q").unwrap_or_default();
+    let query = Query::from_str(q.as_str()).map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let hits = app.search_pages_use_case().search_pages(&query, all)?;
+    let hits = hits
+        .into_iter()
+        .map(|hit| {
+            let url = PagePath::from(*hit.page_id()).to_string();
+            SearchHitJson {
+                id: hit.page_id().to_string(),
+                obsoleted: hit.is_obsoleted(),
+                score: hit.score(),
+                snippets: hit
+                    .snippets()
+                    .iter()
+                    .map(|snippet| SnippetJson {
+                        fragments: snippet
+                            .fragments()
+                            .into_iter()
+                            .map(|(text, highlighted)| SnippetFragmentJson {
+                                highlighted,
+                                text: text.to_string(),
+                            })
+                            .collect(),
+                        line_number: usize::from(snippet.line_number()),
+                        url: format!("{}#L{}", url, snippet.line_number()),
+                    })
+                    .collect(),
+                title: hit.title().to_string(),
+                url,
+            }
+        })
+        .collect::<Vec<SearchHitJson>>();
+    Ok(HttpResponse::Ok().json(hits))
+}
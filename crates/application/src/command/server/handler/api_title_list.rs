@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use super::helpers::is_all;
+use super::ApiError;
+use crate::json::TitleItemJson;
+use actix_web::{web, HttpResponse};
+use entity::TitlePath;
+use use_case::{HasListTitlesUseCase, ListTitlesUseCase};
+
+pub async fn api_title_list<T: HasListTitlesUseCase>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let all = is_all(&req);
+    let titles = app.list_titles_use_case().list_titles(all)?;
+    let titles = titles
+        .into_iter()
+        .map(|(title, obsoleted)| TitleItemJson {
+            obsoleted,
+            title: title.to_string(),
+            url: TitlePath::from(title).to_string(),
+        })
+        .collect::<Vec<TitleItemJson>>();
+    Ok(HttpResponse::Ok().json(titles))
+}
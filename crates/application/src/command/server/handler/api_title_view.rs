@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use super::helpers::is_all;
+use super::{ApiError, ServerError};
+use crate::json::{PageItemJson, TitleJson};
+use actix_web::{web, HttpResponse};
+use entity::{PagePath, PageTitle, TitlePath};
+use use_case::{HasPageRepository, PageRepository};
+
+pub async fn api_title_view<T: HasPageRepository>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let all = is_all(&req);
+    let params: (String,) = req
+        .match_info()
+        .load()
+        .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let page_graph = app.page_repository().load_page_graph()?;
+    let title = PageTitle::from(params.0);
+    let page_ids = page_graph.titled(&title);
+    if page_ids.is_empty() {
+        return Err(ServerError::TitleNotFound(title.to_string()).into());
+    }
+    let pages = page_ids
+        .into_iter()
+        .filter(|page_id| all || !page_graph.is_obsoleted(page_id))
+        .map(|page_id| PageItemJson {
+            id: page_id.to_string(),
+            obsoleted: page_graph.is_obsoleted(&page_id),
+            url: PagePath::from(page_id).to_string(),
+        })
+        .collect::<Vec<PageItemJson>>();
+    Ok(HttpResponse::Ok().json(TitleJson {
+        pages,
+        title: title.to_string(),
+        url: TitlePath::from(title).to_string(),
+    }))
+}
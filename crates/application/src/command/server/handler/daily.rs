@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use super::helpers::query_param;
+use super::ServerError;
+use crate::config::daily_title_format;
+use actix_web::{http, web, HttpResponse};
+use chrono::{Local, NaiveDate};
+use entity::PagePath;
+use use_case::{DailyPageUseCase, HasDailyPageUseCase};
+
+// redirects to the daily page of `?date=YYYY-MM-DD` (default: today)
+pub async fn daily<T: HasDailyPageUseCase>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
+    let date = match query_param(&req, "date") {
+        Some(s) => NaiveDate::parse_from_str(s.as_str(), "%Y-%m-%d")
+            .map_err(|_| ServerError::BadRequest(format!("invalid date: {}", s)))?,
+        None => Local::today().naive_local(),
+    };
+    let format = daily_title_format()?;
+    let (page_id, _) = app.daily_page_use_case().daily_page(date, &format)?;
+    Ok(HttpResponse::SeeOther()
+        .set_header(http::header::LOCATION, PagePath::from(page_id).to_string())
+        .body(""))
+}
@@ -1,13 +1,147 @@
+use actix_web::{
+    http::header::{EntityTag, Header, IfMatch},
+    HttpResponse,
+};
+use askama::Template;
+use entity::{PageContent, PageTitle, TitlePath};
+use use_case::{HasPageContentTemplateRepository, PageContentTemplateRepository};
+
+use super::ServerError;
+use crate::template::TitleNotFoundTemplate;
+
+// true if the client prefers `application/json` to `text/html`
+pub fn accepts_json(req: &actix_web::HttpRequest) -> bool {
+    let accept = match req
+        .headers()
+        .get(actix_web::http::header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(accept) => accept,
+        None => return false,
+    };
+    let mut json = 0_f32;
+    let mut html = 0_f32;
+    for media_range in accept.split(',') {
+        let mut params = media_range.split(';').map(str::trim);
+        let media_type = params.next().unwrap_or_default();
+        let q = params
+            .find_map(|param| param.strip_prefix("q="))
+            .and_then(|q| q.parse::<f32>().ok())
+            .unwrap_or(1_f32);
+        match media_type {
+            "application/json" => json = json.max(q),
+            "text/html" => html = html.max(q),
+            _ => {}
+        }
+    }
+    json > 0_f32 && json > html
+}
+
+// true if the `If-Match` header is missing or matches the etag
+pub fn if_match(req: &actix_web::HttpRequest, etag: &EntityTag) -> Result<bool, ServerError> {
+    if !req
+        .headers()
+        .contains_key(actix_web::http::header::IF_MATCH)
+    {
+        return Ok(true);
+    }
+    match IfMatch::parse(req).map_err(|e| ServerError::BadRequest(e.to_string()))? {
+        IfMatch::Any => Ok(true),
+        IfMatch::Items(etags) => Ok(etags.iter().any(|e| e.strong_eq(etag))),
+    }
+}
+
 pub fn is_all(req: &actix_web::HttpRequest) -> bool {
+    query_param(req, "all") == Some("true".to_owned())
+}
+
+// derived from the content (FNV-1a), so it doesn't change on restart
+pub fn page_etag(page_content: &PageContent) -> EntityTag {
+    let hash = page_content
+        .as_str()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+            (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+    EntityTag::strong(format!("{:016x}", hash))
+}
+
+pub fn query_param(req: &actix_web::HttpRequest, name: &str) -> Option<String> {
     use std::str::FromStr;
     match url::Url::from_str(&format!("http://example.com{}", req.uri().to_string())) {
-        Err(_) => false,
+        Err(_) => None,
         Ok(url) => {
             let map = url
                 .query_pairs()
                 .into_owned()
                 .collect::<std::collections::HashMap<String, String>>();
-            map.get("all") == Some(&"true".to_owned())
+            map.get(name).cloned()
+        }
+    }
+}
+
+// offers to create the page (with a template)
+pub fn title_not_found<T: HasPageContentTemplateRepository>(
+    app: &T,
+    title: &PageTitle,
+) -> Result<HttpResponse, ServerError> {
+    let templates = app.page_content_template_repository().find_names()?;
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    serializer.append_pair("title", title.as_str());
+    let template = TitleNotFoundTemplate {
+        new_url: &format!("/pages/new?{}", serializer.finish()),
+        templates: &templates,
+        title: title.as_str(),
+        title_url: &TitlePath::from(title.clone()).to_string(),
+    };
+    let html = template.render()?;
+    Ok(HttpResponse::NotFound()
+        .content_type("text/html")
+        .body(html))
+}
+
+// renders markdown with `L{line_number}` anchors (e.g. `/pages/{id}#L3`)
+pub fn markdown_to_html(md: &str) -> String {
+    use pulldown_cmark::{html, Event, Parser, Tag};
+
+    let line_starts = std::iter::once(0)
+        .chain(md.match_indices('\n').map(|(index, _)| index + 1))
+        .collect::<Vec<usize>>();
+    let line_number = |offset: usize| match line_starts.binary_search(&offset) {
+        Ok(index) => index + 1,
+        Err(index) => index,
+    };
+    let mut anchored = 0;
+    let mut in_image = false;
+    let mut events = vec![];
+    for (event, range) in Parser::new(md).into_offset_iter() {
+        let inline = match &event {
+            Event::Start(Tag::Image(..)) => {
+                in_image = true;
+                true
+            }
+            Event::End(Tag::Image(..)) => {
+                in_image = false;
+                false
+            }
+            // the alt text of an image can't contain any elements
+            _ if in_image => false,
+            Event::Start(Tag::Emphasis)
+            | Event::Start(Tag::Strong)
+            | Event::Start(Tag::Strikethrough)
+            | Event::Start(Tag::Link(..))
+            | Event::Text(_)
+            | Event::Code(_) => true,
+            _ => false,
+        };
+        let line = line_number(range.start);
+        if inline && line > anchored {
+            events.push(Event::Html(format!("<a id=\"L{}\"></a>", line).into()));
+            anchored = line;
         }
+        events.push(event);
     }
+    let mut html = String::new();
+    html::push_html(&mut html, events.into_iter());
+    html
 }
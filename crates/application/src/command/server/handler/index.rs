@@ -1,9 +1,10 @@
+use super::ServerError;
 use crate::template::IndexTemplate;
 use actix_web::HttpResponse;
 use askama::Template;
 
-pub async fn index() -> actix_web::Result<HttpResponse> {
+pub async fn index() -> Result<HttpResponse, ServerError> {
     let template = IndexTemplate {};
-    let html = template.render().map_err(|_| actix_web::Error::from(()))?;
+    let html = template.render()?;
     Ok(HttpResponse::Ok().content_type("text/html").body(html))
 }
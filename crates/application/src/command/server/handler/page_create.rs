@@ -1,32 +1,22 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
+use super::ServerError;
 use actix_web::{http, web, HttpResponse};
-use anyhow::Context;
-use entity::{Page, PageContent, PageId};
-use use_case::{HasPageRepository, PageRepository};
+use entity::PageContent;
+use use_case::{CreatePageUseCase, HasCreatePageUseCase};
 
 #[derive(serde::Deserialize)]
 pub struct FormData {
     content: String,
 }
 
-pub async fn page_create<T: HasPageRepository>(
-    data: web::Data<Arc<Mutex<T>>>,
+pub async fn page_create<T: HasCreatePageUseCase>(
+    data: web::Data<Arc<T>>,
     form: web::Form<FormData>,
-) -> actix_web::Result<HttpResponse> {
-    let app = data
-        .get_ref()
-        .lock()
-        .map_err(|_| actix_web::Error::from(()))?;
-    let page_id = PageId::new()
-        .context("This application is out of date")
-        .map_err(|_| actix_web::Error::from(()))?;
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
     let page_content = PageContent::from(form.0.content);
-    // TODO: UseCase
-    let page = Page::new(page_id, page_content);
-    app.page_repository()
-        .save(page)
-        .map_err(|_| actix_web::Error::from(()))?;
+    let page_id = app.create_page_use_case().create_page(page_content)?;
     Ok(HttpResponse::SeeOther()
         .set_header(http::header::LOCATION, format!("/pages/{}", page_id))
         .body(""))
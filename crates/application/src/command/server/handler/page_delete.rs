@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use super::{ApiError, ServerError};
+use crate::json::{PageDeleteJson, PageItemJson};
+use actix_web::{web, HttpResponse};
+use entity::{PageId, PagePath};
+use use_case::{DeletePageUseCase, HasDeletePageUseCase, HasPageRepository, PageRepository};
+
+#[derive(serde::Deserialize)]
+pub struct PageDeletePath {
+    // `{id}` in `/pages/{id}`
+    #[serde(rename = "id")]
+    page_id: String,
+}
+
+pub async fn page_delete<T: HasDeletePageUseCase + HasPageRepository>(
+    data: web::Data<Arc<T>>,
+    path: web::Path<PageDeletePath>,
+) -> Result<HttpResponse, ApiError> {
+    let app = data.get_ref();
+    let page_id = PageId::from_like_str(path.page_id.as_str())
+        .map_err(|_| ServerError::InvalidPageId(path.page_id.clone()))?;
+    if app.page_repository().find_by_id(&page_id)?.is_none() {
+        return Err(ServerError::PageNotFound(page_id.to_string()).into());
+    }
+    let (linked_by, obsoleted_by) = app.delete_page_use_case().delete_page(&page_id)?;
+    let page_graph = app.page_repository().load_page_graph()?;
+    let page_items = |page_ids: Vec<PageId>| {
+        page_ids
+            .into_iter()
+            .map(|page_id| PageItemJson {
+                id: page_id.to_string(),
+                obsoleted: page_graph.is_obsoleted(&page_id),
+                url: PagePath::from(page_id).to_string(),
+            })
+            .collect::<Vec<PageItemJson>>()
+    };
+    // the references are left as is (the client may warn about them)
+    Ok(HttpResponse::Ok().json(PageDeleteJson {
+        id: page_id.to_string(),
+        linked_by: page_items(linked_by.into_iter().collect()),
+        obsoleted_by: page_items(obsoleted_by.into_iter().collect()),
+    }))
+}
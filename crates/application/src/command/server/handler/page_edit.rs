@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use super::helpers::page_etag;
+use super::ServerError;
+use actix_web::{http, web, HttpResponse};
+use entity::{Page, PageContent, PageId};
+use use_case::{
+    EditPageUseCase, HasEditPageUseCase, HasPageRepository, PageObsoletedError, PageRepository,
+};
+
+#[derive(serde::Deserialize)]
+pub struct PageEditPath {
+    // `{id}` in `/pages/{id}/edit`
+    #[serde(rename = "id")]
+    page_id: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct PageEditForm {
+    content: String,
+    // the etag of the page when the form is shown
+    etag: Option<String>,
+}
+
+// the latest version of the page (`409 Conflict`)
+fn obsoleted_response<T: HasPageRepository>(
+    app: &T,
+    page_id: &PageId,
+) -> Result<HttpResponse, ServerError> {
+    let page_graph = app.page_repository().load_page_graph()?;
+    let latest = page_graph
+        .obsoletion_chain(page_id)
+        .into_iter()
+        .filter(|page_id| !page_graph.is_obsoleted(page_id))
+        .max()
+        .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+    let latest = app
+        .page_repository()
+        .find_by_id(&latest)?
+        .ok_or_else(|| ServerError::PageNotFound(latest.to_string()))?;
+    Ok(conflict_response(&latest))
+}
+
+fn conflict_response(current: &Page) -> HttpResponse {
+    HttpResponse::Conflict()
+        .set_header(
+            http::header::CONTENT_LOCATION,
+            format!("/pages/{}", current.id()),
+        )
+        .content_type("text/markdown; charset=utf-8")
+        .body(current.content().as_str().to_string())
+}
+
+// saves the form as a new page that obsoletes the page (like `rust-memo edit`)
+pub async fn page_edit<T: HasEditPageUseCase + HasPageRepository>(
+    data: web::Data<Arc<T>>,
+    path: web::Path<PageEditPath>,
+    form: web::Form<PageEditForm>,
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
+    let page_id = PageId::from_like_str(path.page_id.as_str())
+        .map_err(|_| ServerError::InvalidPageId(path.page_id.clone()))?;
+    let current = app
+        .page_repository()
+        .find_by_id(&page_id)?
+        .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+    // another version has been created since the form is shown
+    if app
+        .page_repository()
+        .load_page_graph()?
+        .is_obsoleted(&page_id)
+    {
+        return obsoleted_response(app.as_ref(), &page_id);
+    }
+    let form = form.into_inner();
+    if let Some(etag) = form.etag.filter(|etag| !etag.is_empty()) {
+        if etag != page_etag(current.content()).to_string() {
+            return Ok(conflict_response(&current));
+        }
+    }
+    let new_page_id = match app
+        .edit_page_use_case()
+        .edit_page_content(&page_id, PageContent::from(form.content))
+    {
+        Ok(new_page_id) => new_page_id,
+        // obsoleted after the check above
+        Err(e) if e.is::<PageObsoletedError>() => {
+            return obsoleted_response(app.as_ref(), &page_id);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    Ok(HttpResponse::SeeOther()
+        .set_header(http::header::LOCATION, format!("/pages/{}", new_page_id))
+        .body(""))
+}
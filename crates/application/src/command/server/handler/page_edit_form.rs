@@ -0,0 +1,39 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::helpers::page_etag;
+use super::ServerError;
+use crate::template::PageFormTemplate;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use entity::{PageId, PagePath};
+use use_case::{HasPageRepository, PageRepository};
+
+pub async fn page_edit_form<T: HasPageRepository>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
+    let params: (String,) = req
+        .match_info()
+        .load()
+        .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let page_id =
+        PageId::from_str(&params.0).map_err(|_| ServerError::InvalidPageId(params.0.clone()))?;
+    let page = app
+        .page_repository()
+        .find_by_id(&page_id)?
+        .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+    let page_url = PagePath::from(page_id).to_string();
+    // shows the content as it will be saved
+    let mut page_content = page.content().clone();
+    page_content.replace_obsoletes(page_id);
+    let template = PageFormTemplate {
+        action: &format!("{}/edit", page_url),
+        cancel_url: &page_url,
+        content: page_content.as_str(),
+        etag: &page_etag(page.content()).to_string(),
+        heading: &format!("edit {}", page_id),
+    };
+    let html = template.render()?;
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use super::ServerError;
+use actix_web::{http, web, HttpResponse};
+use entity::{PageId, PageTitle};
+use use_case::{
+    HasPageRepository, HasUnlinkedMentionsUseCase, PageRepository, UnlinkedMentionsUseCase,
+};
+
+#[derive(serde::Deserialize)]
+pub struct PageLinkMentionsPath {
+    // `{id}` in `/pages/{id}/link-mentions`
+    #[serde(rename = "id")]
+    page_id: String,
+}
+
+#[derive(serde::Deserialize)]
+pub struct PageLinkMentionsForm {
+    title: String,
+}
+
+// links the mentions of the title in a new page that obsoletes the page (like `rust-memo mentions --link`)
+pub async fn page_link_mentions<T: HasPageRepository + HasUnlinkedMentionsUseCase>(
+    data: web::Data<Arc<T>>,
+    path: web::Path<PageLinkMentionsPath>,
+    form: web::Form<PageLinkMentionsForm>,
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
+    let page_id = PageId::from_like_str(path.page_id.as_str())
+        .map_err(|_| ServerError::InvalidPageId(path.page_id.clone()))?;
+    if app.page_repository().find_by_id(&page_id)?.is_none() {
+        return Err(ServerError::PageNotFound(page_id.to_string()));
+    }
+    let page_title = PageTitle::from(form.into_inner().title);
+    let new_page_id = app
+        .unlinked_mentions_use_case()
+        .link_mentions(&page_id, &page_title)?;
+    Ok(HttpResponse::SeeOther()
+        .set_header(http::header::LOCATION, format!("/pages/{}", new_page_id))
+        .body(""))
+}
@@ -1,26 +1,26 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
-use super::helpers::is_all;
+use super::helpers::{accepts_json, is_all};
+use super::{api_page_list, ServerError};
 use crate::template::{PageItemTemplate, PagesTemplate};
 use crate::url_helpers::pages_url;
-use actix_web::{web::Data, HttpResponse};
+use actix_web::{web::Data, HttpResponse, ResponseError};
 use askama::Template;
 use entity::PagePath;
 use use_case::{HasListPagesUseCase, ListPagesUseCase};
 
 pub async fn page_list<T: HasListPagesUseCase>(
     req: actix_web::HttpRequest,
-    data: Data<Arc<Mutex<T>>>,
-) -> actix_web::Result<HttpResponse> {
-    let app = data
-        .get_ref()
-        .lock()
-        .map_err(|_| actix_web::Error::from(()))?;
+    data: Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    if accepts_json(&req) {
+        return api_page_list(req, data)
+            .await
+            .or_else(|e| Ok(e.error_response()));
+    }
+    let app = data.get_ref();
     let all = is_all(&req);
-    let pages = app
-        .list_pages_use_case()
-        .list_pages(all)
-        .map_err(|_| actix_web::Error::from(()))?;
+    let pages = app.list_pages_use_case().list_pages(all)?;
     let pages = pages
         .into_iter()
         .map(|page| PageItemTemplate {
@@ -33,6 +33,6 @@ pub async fn page_list<T: HasListPagesUseCase>(
         title: &pages_url(),
         pages: &pages,
     };
-    let html = template.render().map_err(|_| actix_web::Error::from(()))?;
+    let html = template.render()?;
     Ok(HttpResponse::Ok().content_type("text/html").body(html))
 }
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use super::ServerError;
+use actix_web::{http, web, HttpResponse};
+use entity::{PagePath, PageTitle};
+use use_case::{
+    HasNewPageFromTemplateUseCase, HasNewPageUseCase, NewPageFromTemplateUseCase, NewPageUseCase,
+};
+
+#[derive(serde::Deserialize)]
+pub struct PageNewForm {
+    // empty if no template is selected
+    template: String,
+    title: String,
+}
+
+// creates a page from the template picker of the title-not-found page
+pub async fn page_new<T: HasNewPageFromTemplateUseCase + HasNewPageUseCase>(
+    data: web::Data<Arc<T>>,
+    form: web::Form<PageNewForm>,
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
+    let form = form.into_inner();
+    let page_title = PageTitle::from(form.title);
+    let (page_id, _) = if form.template.is_empty() {
+        app.new_page_use_case().new_page(page_title)?
+    } else {
+        app.new_page_from_template_use_case()
+            .new_page_from_template(page_title, form.template.as_str())?
+    };
+    Ok(HttpResponse::SeeOther()
+        .set_header(http::header::LOCATION, PagePath::from(page_id).to_string())
+        .body(""))
+}
@@ -0,0 +1,20 @@
+use super::helpers::query_param;
+use super::ServerError;
+use crate::template::PageFormTemplate;
+use actix_web::HttpResponse;
+use askama::Template;
+
+pub async fn page_new_form(req: actix_web::HttpRequest) -> Result<HttpResponse, ServerError> {
+    let content = query_param(&req, "title")
+        .map(|title| format!("# {}\n\n", title))
+        .unwrap_or_default();
+    let template = PageFormTemplate {
+        action: "/pages",
+        cancel_url: "/pages",
+        content: content.as_str(),
+        etag: "",
+        heading: "new page",
+    };
+    let html = template.render()?;
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
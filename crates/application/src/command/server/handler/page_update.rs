@@ -1,11 +1,19 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
-use actix_web::{http, web, HttpResponse};
+use super::helpers::{accepts_json, if_match, page_etag};
+use super::ServerError;
+use crate::json::PageConflictJson;
+use actix_web::{
+    http::{self, header::ETag},
+    web, HttpResponse,
+};
 use entity::{Page, PageContent, PageId};
 use use_case::{HasPageRepository, PageRepository};
 
 #[derive(serde::Deserialize)]
 pub struct PageUpdatePath {
+    // `{id}` in `/pages/{id}`
+    #[serde(rename = "id")]
     page_id: String,
 }
 
@@ -14,24 +22,66 @@ pub struct PageUpdateForm {
     content: String,
 }
 
+// the current content and its etag (`409 Conflict`)
+fn conflict_response(req: &actix_web::HttpRequest, current: &Page) -> HttpResponse {
+    let etag = page_etag(current.content());
+    let content = current.content().as_str().to_string();
+    let mut response = HttpResponse::Conflict();
+    response.set(ETag(etag.clone()));
+    if accepts_json(req) {
+        response.json(PageConflictJson {
+            content,
+            etag: etag.to_string(),
+            message: format!("page has been modified: {}", current.id()),
+        })
+    } else {
+        response
+            .content_type("text/markdown; charset=utf-8")
+            .body(content)
+    }
+}
+
 pub async fn page_update<T: HasPageRepository>(
-    data: web::Data<Arc<Mutex<T>>>,
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
     path: web::Path<PageUpdatePath>,
     form: web::Form<PageUpdateForm>,
-) -> actix_web::Result<HttpResponse> {
-    let app = data
-        .get_ref()
-        .lock()
-        .map_err(|_| actix_web::Error::from(()))?;
-    let page_id =
-        PageId::from_like_str(path.page_id.as_str()).map_err(|_| actix_web::Error::from(()))?;
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
+    let page_id = PageId::from_like_str(path.page_id.as_str())
+        .map_err(|_| ServerError::InvalidPageId(path.page_id.clone()))?;
+    let has_if_match = req.headers().contains_key(http::header::IF_MATCH);
+    // rejects the update if the page has changed since the client read it
+    let current = match app.page_repository().find_by_id(&page_id)? {
+        Some(current) => {
+            if !if_match(&req, &page_etag(current.content()))? {
+                return Ok(conflict_response(&req, &current));
+            }
+            Some(current)
+        }
+        None if has_if_match => {
+            return Err(ServerError::PageNotFound(page_id.to_string()));
+        }
+        None => None,
+    };
     let page_content = PageContent::from(form.into_inner().content);
+    let etag = page_etag(&page_content);
     // TODO: UseCase
     let page = Page::new(page_id, page_content);
-    app.page_repository()
-        .save(page)
-        .map_err(|_| actix_web::Error::from(()))?;
+    match current.filter(|_| has_if_match) {
+        // the page may be updated by another request after the check above
+        Some(current) => {
+            if !app.page_repository().save_if(page, current.content())? {
+                return match app.page_repository().find_by_id(&page_id)? {
+                    Some(current) => Ok(conflict_response(&req, &current)),
+                    None => Err(ServerError::PageNotFound(page_id.to_string())),
+                };
+            }
+        }
+        None => app.page_repository().save(page)?,
+    }
     Ok(HttpResponse::SeeOther()
+        .set(ETag(etag))
         .set_header(http::header::LOCATION, format!("/pages/{}", page_id))
         .body(""))
 }
@@ -1,64 +1,135 @@
-use std::{
-    str::FromStr,
-    sync::{Arc, Mutex},
-};
+use std::{collections::BTreeSet, str::FromStr, sync::Arc};
 
-use super::helpers::is_all;
+use super::helpers::{accepts_json, is_all, markdown_to_html, page_etag};
+use super::{api_page_view, ServerError};
 use crate::template::{PageItemTemplate, PageTemplate, PageWithTitle};
-use actix_web::{web, HttpResponse, ResponseError};
+use actix_web::{http::header::ETag, web, HttpResponse, ResponseError};
 use askama::Template;
-use entity::{PageId, PagePath, TitlePath};
-use thiserror::Error;
-use use_case::{HasPageRepository, PageRepository};
+use entity::{PageGraph, PageId, PageLinkStyle, PageLinkTo, PagePath, PageTitle, TitlePath};
+use use_case::{
+    HasPageRepository, HasUnlinkedMentionsUseCase, PageRepository, UnlinkedMentionsUseCase,
+};
 
-// TODO:
-#[derive(Debug, Error)]
-#[error("error")]
-struct MyError(String);
+// the pages that link to `to` with the lines that contain the links
+fn linked_by_items<T: HasPageRepository>(
+    app: &T,
+    page_graph: &PageGraph,
+    page_ids: BTreeSet<PageId>,
+    to: &PageLinkTo,
+    all: bool,
+) -> Result<Vec<PageWithTitle>, ServerError> {
+    page_ids
+        .into_iter()
+        .filter(|page_id| all || !page_graph.is_obsoleted(page_id))
+        .map(|page_id| {
+            let page = app
+                .page_repository()
+                .find_by_id(&page_id)?
+                .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+            let lines = page.content().as_str().lines().collect::<Vec<&str>>();
+            let line_numbers = page
+                .links()
+                .into_iter()
+                .filter(|page_link| page_link.to() == to)
+                .map(|page_link| usize::from(page_link.span().line_number()))
+                .collect::<BTreeSet<usize>>();
+            Ok(PageWithTitle {
+                id: page_id.to_string(),
+                lines: line_numbers
+                    .into_iter()
+                    .filter_map(|line_number| lines.get(line_number - 1))
+                    .map(|line| line.trim().to_string())
+                    .collect(),
+                obsoleted: page_graph.is_obsoleted(&page_id),
+                title: page.title().to_string(),
+                url: PagePath::from(page_id).to_string(),
+            })
+        })
+        .collect::<Result<Vec<PageWithTitle>, ServerError>>()
+}
 
-impl ResponseError for MyError {}
+// the pages that mention the title without linking it with the lines that contain the mentions
+fn unlinked_mentions_items<T: HasPageRepository + HasUnlinkedMentionsUseCase>(
+    app: &T,
+    page_graph: &PageGraph,
+    title: &PageTitle,
+) -> Result<Vec<PageWithTitle>, ServerError> {
+    app.unlinked_mentions_use_case()
+        .find_unlinked_mentions(title)?
+        .into_iter()
+        .map(|(page_id, spans)| {
+            let page = app
+                .page_repository()
+                .find_by_id(&page_id)?
+                .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+            let lines = page.content().as_str().lines().collect::<Vec<&str>>();
+            let line_numbers = spans
+                .iter()
+                .map(|span| usize::from(span.line_number()))
+                .collect::<BTreeSet<usize>>();
+            Ok(PageWithTitle {
+                id: page_id.to_string(),
+                lines: line_numbers
+                    .into_iter()
+                    .filter_map(|line_number| lines.get(line_number - 1))
+                    .map(|line| line.trim().to_string())
+                    .collect(),
+                obsoleted: page_graph.is_obsoleted(&page_id),
+                title: page.title().to_string(),
+                url: PagePath::from(page_id).to_string(),
+            })
+        })
+        .collect::<Result<Vec<PageWithTitle>, ServerError>>()
+}
 
-pub async fn page_view<T: HasPageRepository>(
+pub async fn page_view<T: HasPageRepository + HasUnlinkedMentionsUseCase>(
     req: actix_web::HttpRequest,
-    data: web::Data<Arc<Mutex<T>>>,
-) -> actix_web::Result<HttpResponse> {
-    let app = data
-        .get_ref()
-        .lock()
-        .map_err(|_| actix_web::Error::from(()))?;
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    if accepts_json(&req) {
+        return api_page_view(req, data)
+            .await
+            .or_else(|e| Ok(e.error_response()));
+    }
+    let app = data.get_ref();
     let all = is_all(&req);
-    let params: (String,) = req.match_info().load()?;
-    let page_id = PageId::from_str(&params.0)
-        .map_err(|_| std::io::Error::new(std::io::ErrorKind::NotFound, "invalid page_id format"))?;
+    let params: (String,) = req
+        .match_info()
+        .load()
+        .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let page_id =
+        PageId::from_str(&params.0).map_err(|_| ServerError::InvalidPageId(params.0.clone()))?;
     let title = app
         .page_repository()
-        .find_by_id(&page_id)
-        .map_err(|_| MyError(format!("IO Error: {}", page_id)))?
+        .find_by_id(&page_id)?
         .map(|page_content| page_content.title())
-        .ok_or_else(|| MyError(format!("page_id not found: {}", page_id)))?;
-    let page_graph = app
-        .page_repository()
-        .load_page_graph()
-        .map_err(|_| actix_web::Error::from(()))?;
-    let linked_by = page_graph
-        .find_ids_link_to(&title)
+        .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+    let page_graph = app.page_repository().load_page_graph()?;
+    let linked_by = linked_by_items(
+        app.as_ref(),
+        &page_graph,
+        page_graph.find_ids_link_to(&title),
+        &PageLinkTo::Title(title.clone()),
+        all,
+    )?;
+    // the pages that link to this version (not to the title)
+    let page_linked_by = linked_by_items(
+        app.as_ref(),
+        &page_graph,
+        page_graph.find_ids_link_to_page(&page_id),
+        &PageLinkTo::Page(page_id),
+        all,
+    )?;
+    let unlinked_mentions = unlinked_mentions_items(app.as_ref(), &page_graph, &title)?;
+    let obsoleted_links = page_graph
+        .obsoleted_page_links(&page_id)
         .iter()
-        .filter(|page_id| all || !page_graph.is_obsoleted(page_id))
-        .map(|page_id| {
-            let title = app
-                .page_repository()
-                .find_by_id(page_id)
-                .map_err(|_| MyError(format!("IO Error: {}", page_id)))?
-                .map(|page_content| page_content.title())
-                .ok_or_else(|| MyError(format!("page_id not found: {}", page_id)))?;
-            Ok(PageWithTitle {
-                id: page_id.to_string(),
-                obsoleted: page_graph.is_obsoleted(page_id),
-                title: title.to_string(),
-                url: PagePath::from(*page_id).to_string(),
-            })
+        .map(|page_id| PageItemTemplate {
+            id: page_id.to_string(),
+            obsoleted: true,
+            url: PagePath::from(*page_id).to_string(),
         })
-        .collect::<actix_web::Result<Vec<PageWithTitle>>>()?;
+        .collect::<Vec<PageItemTemplate>>();
     let obsoleted_by = page_graph
         .obsoleted_by(&page_id)
         .iter()
@@ -68,20 +139,19 @@ pub async fn page_view<T: HasPageRepository>(
             url: PagePath::from(*page_id).to_string(),
         })
         .collect::<Vec<PageItemTemplate>>();
-    let md = app
+    let page = app
         .page_repository()
-        .find_by_id(&page_id)
-        .map_err(|_| MyError(format!("IO error: {}", page_id)))?
-        .map(|page| {
-            let mut page_content = page.content().clone();
-            page_content.ensure_links();
-            page_content
-        })
-        .map(String::from)
-        .ok_or_else(|| MyError(format!("file not found: {}", page_id)))?;
-    let parser = pulldown_cmark::Parser::new(&md);
-    let mut markdown_html = String::new();
-    pulldown_cmark::html::push_html(&mut markdown_html, parser);
+        .find_by_id(&page_id)?
+        .ok_or_else(|| ServerError::PageNotFound(page_id.to_string()))?;
+    // the etag for `If-Match` of `PATCH /pages/{id}`
+    let etag = page_etag(page.content());
+    let md = {
+        let mut page_content = page.content().clone();
+        page_content.convert_links(PageLinkStyle::Markdown);
+        page_content.ensure_links();
+        String::from(page_content)
+    };
+    let markdown_html = markdown_to_html(&md);
     let template = PageTemplate {
         linked_by: &linked_by,
         page_id: &page_id.to_string(),
@@ -90,7 +160,13 @@ pub async fn page_view<T: HasPageRepository>(
         title_url: &TitlePath::from(title.clone()).to_string(),
         html: markdown_html,
         obsoleted_by: &obsoleted_by,
+        obsoleted_links: &obsoleted_links,
+        page_linked_by: &page_linked_by,
+        unlinked_mentions: &unlinked_mentions,
     };
-    let html = template.render().map_err(|_| actix_web::Error::from(()))?;
-    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+    let html = template.render()?;
+    Ok(HttpResponse::Ok()
+        .set(ETag(etag))
+        .content_type("text/html")
+        .body(html))
 }
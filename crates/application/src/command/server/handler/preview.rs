@@ -0,0 +1,17 @@
+use super::helpers::markdown_to_html;
+use actix_web::{web, HttpResponse};
+use entity::{PageContent, PageLinkStyle};
+
+#[derive(serde::Deserialize)]
+pub struct PreviewForm {
+    content: String,
+}
+
+// renders the markdown for the live preview of the page form
+pub async fn preview(form: web::Form<PreviewForm>) -> HttpResponse {
+    let mut page_content = PageContent::from(form.into_inner().content);
+    page_content.convert_links(PageLinkStyle::Markdown);
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(markdown_to_html(page_content.as_str()))
+}
@@ -0,0 +1,83 @@
+use std::{str::FromStr, sync::Arc};
+
+use super::helpers::{accepts_json, is_all, query_param};
+use super::{api_search, ServerError};
+use crate::template::{
+    SearchItemTemplate, SearchSnippetTemplate, SearchTemplate, SnippetFragmentTemplate,
+};
+use actix_web::{web, HttpResponse, ResponseError};
+use askama::Template;
+use entity::{PagePath, Query};
+use use_case::{HasSearchPagesUseCase, SearchPagesUseCase};
+
+fn search_url(q: &str, all: bool) -> String {
+    let mut serializer = url::form_urlencoded::Serializer::new(String::new());
+    serializer.append_pair("q", q);
+    if all {
+        serializer.append_pair("all", "true");
+    }
+    format!("/search?{}", serializer.finish())
+}
+
+pub async fn search<T: HasSearchPagesUseCase>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    if accepts_json(&req) {
+        return api_search(req, data)
+            .await
+            .or_else(|e| Ok(e.error_response()));
+    }
+    let app = data.get_ref();
+    let all = is_all(&req);
+    let q = query_param(&req, "q").unwrap_or_default();
+    let (error, hits) = if q.is_empty() {
+        (String::new(), vec![])
+    } else {
+        match Query::from_str(q.as_str()) {
+            Err(e) => (e.to_string(), vec![]),
+            Ok(query) => (
+                String::new(),
+                app.search_pages_use_case().search_pages(&query, all)?,
+            ),
+        }
+    };
+    let hits = hits
+        .into_iter()
+        .map(|hit| {
+            let url = PagePath::from(*hit.page_id()).to_string();
+            SearchItemTemplate {
+                id: hit.page_id().to_string(),
+                obsoleted: hit.is_obsoleted(),
+                snippets: hit
+                    .snippets()
+                    .iter()
+                    .map(|snippet| SearchSnippetTemplate {
+                        fragments: snippet
+                            .fragments()
+                            .into_iter()
+                            .map(|(text, highlighted)| SnippetFragmentTemplate {
+                                highlighted,
+                                text: text.to_string(),
+                            })
+                            .collect(),
+                        line_number: usize::from(snippet.line_number()),
+                        url: format!("{}#L{}", url, snippet.line_number()),
+                    })
+                    .collect(),
+                title: hit.title().to_string(),
+                url,
+            }
+        })
+        .collect::<Vec<SearchItemTemplate>>();
+    let template = SearchTemplate {
+        all_url: &search_url(q.as_str(), true),
+        error: error.as_str(),
+        hits: &hits,
+        no_obsoleted_url: &search_url(q.as_str(), false),
+        query: q.as_str(),
+        show_all: all,
+    };
+    let html = template.render()?;
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
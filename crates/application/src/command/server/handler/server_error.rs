@@ -0,0 +1,150 @@
+use crate::json::ErrorJson;
+use crate::template::ErrorTemplate;
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use askama::Template;
+use thiserror::Error;
+use use_case::{LockPoisonedError, PageObsoletedError};
+
+#[derive(Debug, Error)]
+pub enum ServerError {
+    #[error("bad request: {0}")]
+    BadRequest(String),
+    #[error("internal error: {0}")]
+    Internal(anyhow::Error),
+    #[error("invalid page id: {0}")]
+    InvalidPageId(String),
+    #[error("IO error")]
+    Io(anyhow::Error),
+    #[error("lock poisoned")]
+    LockPoisoned(anyhow::Error),
+    #[error("page not found: {0}")]
+    PageNotFound(String),
+    #[error("page has been obsoleted: {0}")]
+    PageObsoleted(String),
+    #[error("failed to render the template")]
+    Template(#[from] askama::Error),
+    #[error("title not found: {0}")]
+    TitleNotFound(String),
+}
+
+impl ServerError {
+    fn log(&self) {
+        match self {
+            ServerError::Internal(e) | ServerError::Io(e) | ServerError::LockPoisoned(e) => {
+                eprintln!("error: {}: {:?}", self, e)
+            }
+            ServerError::Template(e) => eprintln!("error: {}: {:?}", self, e),
+            ServerError::BadRequest(_)
+            | ServerError::InvalidPageId(_)
+            | ServerError::PageNotFound(_)
+            | ServerError::PageObsoleted(_)
+            | ServerError::TitleNotFound(_) => {}
+        }
+    }
+
+    fn json_response(&self) -> HttpResponse {
+        self.log();
+        HttpResponse::build(self.status_code()).json(ErrorJson {
+            message: self.to_string(),
+        })
+    }
+}
+
+impl From<anyhow::Error> for ServerError {
+    fn from(e: anyhow::Error) -> Self {
+        if let Some(PageObsoletedError(page_id)) = e.downcast_ref::<PageObsoletedError>() {
+            ServerError::PageObsoleted(page_id.to_string())
+        } else if e.is::<LockPoisonedError>() {
+            ServerError::LockPoisoned(e)
+        } else if e.chain().any(|cause| cause.is::<std::io::Error>()) {
+            ServerError::Io(e)
+        } else {
+            ServerError::Internal(e)
+        }
+    }
+}
+
+impl ResponseError for ServerError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ServerError::BadRequest(_) | ServerError::InvalidPageId(_) => StatusCode::BAD_REQUEST,
+            ServerError::PageNotFound(_) | ServerError::TitleNotFound(_) => StatusCode::NOT_FOUND,
+            ServerError::PageObsoleted(_) => StatusCode::CONFLICT,
+            ServerError::Internal(_)
+            | ServerError::Io(_)
+            | ServerError::LockPoisoned(_)
+            | ServerError::Template(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        self.log();
+        let status_code = self.status_code();
+        let template = ErrorTemplate {
+            message: &self.to_string(),
+            status: &status_code.to_string(),
+        };
+        match template.render() {
+            Ok(html) => HttpResponse::build(status_code)
+                .content_type("text/html")
+                .body(html),
+            Err(_) => HttpResponse::build(status_code)
+                .content_type("text/plain")
+                .body(self.to_string()),
+        }
+    }
+}
+
+// renders the error as JSON (for `/api/v1`)
+#[derive(Debug, Error)]
+#[error(transparent)]
+pub struct ApiError(#[from] ServerError);
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        ApiError(ServerError::from(e))
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        self.0.status_code()
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        self.0.json_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use anyhow::{anyhow, Context as _};
+
+    use super::*;
+
+    #[test]
+    fn from_anyhow_error_test() {
+        let e = ServerError::from(anyhow::Error::from(LockPoisonedError("page graph")));
+        assert!(matches!(e, ServerError::LockPoisoned(_)));
+        assert_eq!(e.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let io_error = std::io::Error::from(std::io::ErrorKind::NotFound);
+        let e = ServerError::from(
+            Err::<(), _>(io_error)
+                .context("failed to write")
+                .unwrap_err(),
+        );
+        assert!(matches!(e, ServerError::Io(_)));
+
+        let page_id = entity::PageId::from_str("20210203T040506Z").unwrap();
+        let e = ServerError::from(anyhow::Error::from(PageObsoletedError(page_id)));
+        assert!(matches!(e, ServerError::PageObsoleted(_)));
+        assert_eq!(e.status_code(), StatusCode::CONFLICT);
+
+        let e = ServerError::from(anyhow!("title already exists: foo"));
+        assert!(matches!(e, ServerError::Internal(_)));
+        assert_eq!(e.to_string(), "internal error: title already exists: foo");
+    }
+}
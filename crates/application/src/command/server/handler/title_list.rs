@@ -1,26 +1,26 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
-use super::helpers::is_all;
+use super::helpers::{accepts_json, is_all};
+use super::{api_title_list, ServerError};
 use crate::template::{TitlesItemTemplate, TitlesTemplate};
 use crate::url_helpers::titles_url;
-use actix_web::{web, HttpResponse};
+use actix_web::{web, HttpResponse, ResponseError};
 use askama::Template;
 use entity::TitlePath;
 use use_case::{HasListTitlesUseCase, ListTitlesUseCase};
 
 pub async fn title_list<T: HasListTitlesUseCase>(
     req: actix_web::HttpRequest,
-    data: web::Data<Arc<Mutex<T>>>,
-) -> actix_web::Result<HttpResponse> {
-    let app = data
-        .get_ref()
-        .lock()
-        .map_err(|_| actix_web::Error::from(()))?;
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    if accepts_json(&req) {
+        return api_title_list(req, data)
+            .await
+            .or_else(|e| Ok(e.error_response()));
+    }
+    let app = data.get_ref();
     let all = is_all(&req);
-    let titles = app
-        .list_titles_use_case()
-        .list_titles(all)
-        .map_err(|_| actix_web::Error::from(()))?;
+    let titles = app.list_titles_use_case().list_titles(all)?;
     let titles = titles
         .into_iter()
         .map(|title| TitlesItemTemplate {
@@ -35,6 +35,6 @@ pub async fn title_list<T: HasListTitlesUseCase>(
         title: &titles_url(),
         titles: &titles,
     };
-    let html = template.render().map_err(|_| actix_web::Error::from(()))?;
+    let html = template.render()?;
     Ok(HttpResponse::Ok().content_type("text/html").body(html))
 }
@@ -1,37 +1,33 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 
-use super::helpers::is_all;
-use crate::template::{PageItemTemplate, TitleNotFoundTemplate, TitleTemplate};
-use actix_web::{web, HttpResponse};
+use super::helpers::{accepts_json, is_all, title_not_found};
+use super::{api_title_view, ServerError};
+use crate::template::{PageItemTemplate, TitleTemplate};
+use actix_web::{web, HttpResponse, ResponseError};
 use askama::Template;
 use entity::{PageId, PagePath, PageTitle, TitlePath};
-use use_case::{HasPageRepository, PageRepository};
+use use_case::{HasPageContentTemplateRepository, HasPageRepository, PageRepository};
 
-pub async fn title_view<T: HasPageRepository>(
+pub async fn title_view<T: HasPageContentTemplateRepository + HasPageRepository>(
     req: actix_web::HttpRequest,
-    data: web::Data<Arc<Mutex<T>>>,
-) -> actix_web::Result<HttpResponse> {
-    let app = data
-        .get_ref()
-        .lock()
-        .map_err(|_| actix_web::Error::from(()))?;
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    if accepts_json(&req) {
+        return api_title_view(req, data)
+            .await
+            .or_else(|e| Ok(e.error_response()));
+    }
+    let app = data.get_ref();
     let all = is_all(&req);
-    let params: (String,) = req.match_info().load()?;
-    let page_graph = app
-        .page_repository()
-        .load_page_graph()
-        .map_err(|_| actix_web::Error::from(()))?;
+    let params: (String,) = req
+        .match_info()
+        .load()
+        .map_err(|e| ServerError::BadRequest(e.to_string()))?;
+    let page_graph = app.page_repository().load_page_graph()?;
     let title = PageTitle::from(params.0);
     let page_ids = page_graph.titled(&title);
     if page_ids.is_empty() {
-        let template = TitleNotFoundTemplate {
-            title: title.as_str(),
-            title_url: &TitlePath::from(title.clone()).to_string(),
-        };
-        let html = template.render().map_err(|_| actix_web::Error::from(()))?;
-        Ok(HttpResponse::NotFound()
-            .content_type("text/html")
-            .body(html))
+        title_not_found(app.as_ref(), &title)
     } else {
         let page_ids = page_ids
             .iter()
@@ -59,7 +55,7 @@ pub async fn title_view<T: HasPageRepository>(
                 title_url: &TitlePath::from(title.clone()).to_string(),
                 pages: &pages,
             };
-            let html = template.render().map_err(|_| actix_web::Error::from(()))?;
+            let html = template.render()?;
             Ok(HttpResponse::Ok().content_type("text/html").body(html))
         }
     }
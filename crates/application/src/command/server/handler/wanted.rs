@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use super::helpers::is_all;
+use super::ServerError;
+use crate::template::{PageItemTemplate, WantedItemTemplate, WantedTemplate};
+use crate::url_helpers::wanted_url;
+use actix_web::{web, HttpResponse};
+use askama::Template;
+use entity::{PagePath, TitlePath};
+use use_case::{CheckLinksUseCase, HasCheckLinksUseCase, HasPageRepository, PageRepository};
+
+// the titles that are linked but no page has (like `rust-memo check-links`)
+pub async fn wanted<T: HasCheckLinksUseCase + HasPageRepository>(
+    req: actix_web::HttpRequest,
+    data: web::Data<Arc<T>>,
+) -> Result<HttpResponse, ServerError> {
+    let app = data.get_ref();
+    let all = is_all(&req);
+    let page_graph = app.page_repository().load_page_graph()?;
+    let titles = app
+        .check_links_use_case()
+        .find_wanted_titles(all)?
+        .into_iter()
+        .map(|(page_title, page_ids)| WantedItemTemplate {
+            pages: page_ids
+                .into_iter()
+                .map(|page_id| PageItemTemplate {
+                    id: page_id.to_string(),
+                    obsoleted: page_graph.is_obsoleted(&page_id),
+                    url: PagePath::from(page_id).to_string(),
+                })
+                .collect(),
+            title: page_title.to_string(),
+            url: TitlePath::from(page_title).to_string(),
+        })
+        .collect::<Vec<WantedItemTemplate>>();
+    let template = WantedTemplate {
+        show_all: all,
+        title: &wanted_url(),
+        titles: &titles,
+    };
+    let html = template.render()?;
+    Ok(HttpResponse::Ok().content_type("text/html").body(html))
+}
@@ -0,0 +1,46 @@
+use anyhow::anyhow;
+use entity::PageId;
+use use_case::{HasPageRepository, PageRepository};
+
+use crate::helpers::to_file_name;
+
+pub fn trash_list<App: HasPageRepository>(app: App) -> anyhow::Result<()> {
+    for page_id in app.page_repository().find_trashed_ids()? {
+        let page_title = app
+            .page_repository()
+            .find_trashed_by_id(&page_id)?
+            .map(|page| page.title())
+            .unwrap_or_default();
+        println!("{}\t{}", to_file_name(&page_id), page_title);
+    }
+    Ok(())
+}
+
+pub fn trash_purge<App: HasPageRepository>(
+    app: App,
+    all: bool,
+    id_like: Option<&str>,
+) -> anyhow::Result<()> {
+    let page_ids = match (all, id_like) {
+        (false, Some(id_like)) => vec![PageId::from_like_str(id_like)?],
+        // removes all only if it is explicitly requested
+        (true, None) => app.page_repository().find_trashed_ids()?,
+        _ => return Err(anyhow!("specify either the id or --all")),
+    };
+    for page_id in page_ids {
+        if !app.page_repository().purge(&page_id)? {
+            return Err(anyhow!("not found in the trash: {}", page_id));
+        }
+        println!("{}", to_file_name(&page_id));
+    }
+    Ok(())
+}
+
+pub fn trash_restore<App: HasPageRepository>(app: App, id_like: &str) -> anyhow::Result<()> {
+    let page_id = PageId::from_like_str(id_like)?;
+    if !app.page_repository().restore(&page_id)? {
+        return Err(anyhow!("not found in the trash: {}", page_id));
+    }
+    println!("{}", to_file_name(&page_id));
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use std::{env, str::FromStr};
+
+use anyhow::anyhow;
+use entity::DailyTitleFormat;
+
+// e.g. RUST_MEMO_DAILY_FORMAT="%Y-%m-%d (%a)"
+pub fn daily_title_format() -> anyhow::Result<DailyTitleFormat> {
+    match env::var("RUST_MEMO_DAILY_FORMAT") {
+        Ok(s) => DailyTitleFormat::from_str(s.as_str())
+            .map_err(|_| anyhow!("invalid RUST_MEMO_DAILY_FORMAT: {}", s)),
+        Err(_) => Ok(DailyTitleFormat::default()),
+    }
+}
@@ -0,0 +1,51 @@
+use std::{env, process::Command};
+
+use anyhow::{anyhow, Context};
+use entity::PageId;
+use use_case::{HasPageRepository, PageRepository};
+
+use crate::helpers::to_file_name;
+
+// e.g. RUST_MEMO_OPEN=1
+pub fn is_open_by_default() -> bool {
+    env::var("RUST_MEMO_OPEN")
+        .map(|s| !matches!(s.as_str(), "" | "0" | "false"))
+        .unwrap_or(false)
+}
+
+fn editor_command() -> anyhow::Result<Command> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .map_err(|_| anyhow!("$VISUAL or $EDITOR is not set"))?;
+    // allows arguments (e.g. `code --wait`)
+    let mut words = editor.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| anyhow!("$VISUAL or $EDITOR is empty"))?;
+    let mut command = Command::new(program);
+    command.args(words);
+    Ok(command)
+}
+
+// returns false if the page is deleted because it is empty or unchanged
+pub fn open_new_page<App: HasPageRepository>(app: &App, page_id: &PageId) -> anyhow::Result<bool> {
+    let file_name = to_file_name(page_id);
+    let before = app.page_repository().find_by_id(page_id)?;
+    let status = editor_command()?
+        .arg(file_name.as_str())
+        .status()
+        .with_context(|| anyhow!("failed to launch the editor"))?;
+    if !status.success() {
+        return Err(anyhow!("the editor exited with {}", status));
+    }
+    let after = app.page_repository().find_by_id(page_id)?;
+    let is_empty = after
+        .as_ref()
+        .map(|page| page.content().as_str().trim().is_empty())
+        .unwrap_or(true);
+    if is_empty || before == after {
+        app.page_repository().destroy(page_id)?;
+        return Ok(false);
+    }
+    Ok(true)
+}
@@ -0,0 +1,84 @@
+#[derive(serde::Serialize)]
+pub struct ErrorJson {
+    pub message: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct PageConflictJson {
+    pub content: String,
+    pub etag: String,
+    pub message: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct PageDeleteJson {
+    pub id: String,
+    pub linked_by: Vec<PageItemJson>,
+    pub obsoleted_by: Vec<PageItemJson>,
+}
+
+#[derive(serde::Serialize)]
+pub struct PageItemJson {
+    pub id: String,
+    pub obsoleted: bool,
+    pub url: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct PageWithTitleJson {
+    pub id: String,
+    pub obsoleted: bool,
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct PageJson {
+    pub content: String,
+    pub id: String,
+    pub obsoleted: bool,
+    pub obsoleted_by: Vec<PageItemJson>,
+    pub obsoleted_links: Vec<PageItemJson>,
+    pub obsoletes: Vec<PageItemJson>,
+    pub page_linked_by: Vec<PageItemJson>,
+    pub title: String,
+    pub title_url: String,
+    pub url: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct SearchHitJson {
+    pub id: String,
+    pub obsoleted: bool,
+    pub score: usize,
+    pub snippets: Vec<SnippetJson>,
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct SnippetJson {
+    pub fragments: Vec<SnippetFragmentJson>,
+    pub line_number: usize,
+    pub url: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct SnippetFragmentJson {
+    pub highlighted: bool,
+    pub text: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct TitleItemJson {
+    pub obsoleted: bool,
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(serde::Serialize)]
+pub struct TitleJson {
+    pub pages: Vec<PageItemJson>,
+    pub title: String,
+    pub url: String,
+}
@@ -1,13 +1,16 @@
 mod app;
 mod command;
+mod config;
+mod editor;
 mod helpers;
+mod json;
 mod template;
 mod url_helpers;
 
 use std::env;
 
 use app::App;
-use entity::Query;
+use entity::{PageLinkStyle, QueryOptions};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -18,6 +21,37 @@ struct Opt {
 
 #[derive(Debug, StructOpt)]
 enum Subcommand {
+    #[structopt(
+        name = "check-links",
+        about = "Lists the links to titles that no memo has and the links to obsoleted memos"
+    )]
+    CheckLinks {
+        #[structopt(long = "obsoleted", help = "Checks the links in obsoleted memos")]
+        obsoleted: bool,
+        #[structopt(
+            long = "ci",
+            help = "Exits with a non-zero status if any link is reported"
+        )]
+        ci: bool,
+    },
+    #[structopt(
+        name = "convert-links",
+        about = "Converts the title links to `[[title]]` (wiki) or `[title](/titles/title)` (markdown)"
+    )]
+    ConvertLinks {
+        #[structopt(
+            long = "to",
+            name = "STYLE",
+            possible_values = &["markdown", "wiki"],
+            help = "the link style to convert to"
+        )]
+        to: PageLinkStyle,
+        #[structopt(
+            name = "ID_LIKE",
+            help = "the id of the memo to convert (default: all memos)"
+        )]
+        id_like: Option<String>,
+    },
     #[structopt(name = "create", about = "Creates a new memo")]
     Create {
         #[structopt(
@@ -26,6 +60,42 @@ enum Subcommand {
             help = "Creates a new memo with the specified title"
         )]
         title: Option<String>,
+        #[structopt(
+            long = "template",
+            name = "TEMPLATE",
+            conflicts_with = "URL",
+            help = "Creates a new memo from the template in .rust-memo/templates/TEMPLATE.md ({{date}}, {{id}} and {{title}} are replaced)"
+        )]
+        template: Option<String>,
+        #[structopt(
+            long = "server",
+            name = "URL",
+            help = "Creates a new memo through the running server (e.g. http://localhost:3000)"
+        )]
+        server: Option<String>,
+        #[structopt(
+            long = "open",
+            conflicts_with = "URL",
+            help = "Opens the new memo in $VISUAL or $EDITOR (default: $RUST_MEMO_OPEN)"
+        )]
+        open: bool,
+    },
+    #[structopt(
+        name = "daily",
+        about = "Prints the daily memo of the date (creates it if not found)"
+    )]
+    Daily {
+        #[structopt(
+            long = "date",
+            name = "DATE",
+            help = "the date (YYYY-MM-DD) of the daily memo (default: today)"
+        )]
+        date: Option<String>,
+    },
+    #[structopt(name = "delete", about = "Moves the memo to the trash")]
+    Delete {
+        #[structopt(name = "ID_LIKE", help = "the id of the memo to delete")]
+        id_like: String,
     },
     #[structopt(
         name = "edit",
@@ -37,6 +107,11 @@ enum Subcommand {
             help = "the id or title of the memo to edit"
         )]
         id_like_or_title: String,
+        #[structopt(
+            long = "open",
+            help = "Opens the new memo in $VISUAL or $EDITOR (default: $RUST_MEMO_OPEN)"
+        )]
+        open: bool,
     },
     #[structopt(name = "ensure-links", about = "Ensures the links in the memo")]
     EnsureLinks {
@@ -58,12 +133,64 @@ enum Subcommand {
         #[structopt(long = "obsoleted", help = "Prints obsoleted memo titles")]
         obsoleted: bool,
     },
+    #[structopt(
+        name = "mentions",
+        about = "Lists the unlinked mentions of the title (or links them in the specified memo)"
+    )]
+    Mentions {
+        #[structopt(name = "TITLE", help = "the title to find")]
+        title: String,
+        #[structopt(
+            long = "link",
+            name = "ID_LIKE",
+            help = "Creates a new memo that links the mentions and obsoletes the specified memo"
+        )]
+        link: Option<String>,
+    },
+    #[structopt(name = "reindex", about = "Rebuilds the search index")]
+    Reindex,
+    #[structopt(
+        name = "rename-title",
+        about = "Renames the title and the links to it (creates new memos that obsolete the rewritten memos)"
+    )]
+    RenameTitle {
+        #[structopt(name = "OLD", help = "the title to rename")]
+        from: String,
+        #[structopt(name = "NEW", help = "the new title")]
+        to: String,
+        #[structopt(
+            long = "dry-run",
+            help = "Prints the diff of the memos to rewrite without creating new memos"
+        )]
+        dry_run: bool,
+    },
     #[structopt(name = "search", about = "Searchs by query")]
     Search {
-        #[structopt(name = "QUERY", help = "the query")]
-        query: Query,
+        #[structopt(
+            name = "QUERY",
+            help = "the query (e.g. `foo \"exact phrase\" -bar title:baz links-to:qux obsoleted:false created:>=2021-01-01`)"
+        )]
+        query: String,
         #[structopt(long = "obsoleted", help = "Prints obsoleted memo titles")]
         obsoleted: bool,
+        #[structopt(long = "regex", help = "Treats the terms as regular expressions")]
+        regex: bool,
+        #[structopt(long = "ignore-case", help = "Ignores case distinctions")]
+        ignore_case: bool,
+        #[structopt(long = "word", help = "Matches only whole words")]
+        word: bool,
+        #[structopt(
+            long = "context",
+            name = "NUM",
+            help = "Prints NUM lines of context around each matching line"
+        )]
+        context: Option<usize>,
+        #[structopt(
+            long = "rank",
+            conflicts_with = "NUM",
+            help = "Sorts memos by relevance and prints the title and a snippet"
+        )]
+        rank: bool,
     },
     #[structopt(name = "server", about = "Runs server")]
     Server,
@@ -72,6 +199,34 @@ enum Subcommand {
         #[structopt(name = "ID_LIKE", help = "the id of the memo")]
         id_like: String,
     },
+    #[structopt(name = "trash", about = "Manages the deleted memos")]
+    Trash {
+        #[structopt(subcommand)]
+        subcommand: TrashSubcommand,
+    },
+}
+
+#[derive(Debug, StructOpt)]
+enum TrashSubcommand {
+    #[structopt(name = "list", about = "Lists the deleted memos")]
+    List,
+    #[structopt(name = "purge", about = "Removes the deleted memos permanently")]
+    Purge {
+        #[structopt(
+            long = "all",
+            help = "Removes all deleted memos",
+            conflicts_with = "ID_LIKE",
+            required_unless = "ID_LIKE"
+        )]
+        all: bool,
+        #[structopt(name = "ID_LIKE", help = "the id of the memo to remove")]
+        id_like: Option<String>,
+    },
+    #[structopt(name = "restore", about = "Restores the deleted memo")]
+    Restore {
+        #[structopt(name = "ID_LIKE", help = "the id of the memo to restore")]
+        id_like: String,
+    },
 }
 
 #[actix_rt::main]
@@ -80,14 +235,65 @@ async fn main() -> anyhow::Result<()> {
     let app = App::new(data_dir);
     let opt = Opt::from_args();
     match opt.subcommand {
-        Subcommand::Create { title } => command::create(app, title.as_deref()),
-        Subcommand::Edit { id_like_or_title } => command::edit(app, id_like_or_title.as_str()),
+        Subcommand::CheckLinks { obsoleted, ci } => command::check_links(app, obsoleted, ci),
+        Subcommand::ConvertLinks { to, id_like } => command::convert_links(app, id_like, to),
+        Subcommand::Create {
+            title,
+            template,
+            server,
+            open,
+        } => {
+            let open = server.is_none() && (open || editor::is_open_by_default());
+            command::create(
+                app,
+                title.as_deref(),
+                template.as_deref(),
+                server.as_deref(),
+                open,
+            )
+        }
+        Subcommand::Daily { date } => command::daily(app, date.as_deref()),
+        Subcommand::Delete { id_like } => command::delete(app, id_like.as_str()),
+        Subcommand::Edit {
+            id_like_or_title,
+            open,
+        } => {
+            let open = open || editor::is_open_by_default();
+            command::edit(app, id_like_or_title.as_str(), open)
+        }
         Subcommand::EnsureLinks { id_like } => command::ensure_links(app, id_like),
         Subcommand::Link { id_like_or_title } => command::link(id_like_or_title.as_str()),
         Subcommand::List { obsoleted } => command::list(app, obsoleted),
         Subcommand::ListTitle { obsoleted } => command::list_title(app, obsoleted),
-        Subcommand::Search { obsoleted, query } => command::search(app, query, obsoleted),
+        Subcommand::Mentions { title, link } => command::mentions(app, title.as_str(), link),
+        Subcommand::Reindex => command::reindex(app),
+        Subcommand::RenameTitle { from, to, dry_run } => {
+            command::rename_title(app, from.as_str(), to.as_str(), dry_run)
+        }
+        Subcommand::Search {
+            obsoleted,
+            query,
+            regex,
+            ignore_case,
+            word,
+            context,
+            rank,
+        } => {
+            let options = QueryOptions {
+                ignore_case,
+                regex,
+                word,
+            };
+            command::search(app, query.as_str(), options, obsoleted, context, rank)
+        }
         Subcommand::Server => command::server(app).await,
         Subcommand::Title { id_like } => command::title(app, id_like.as_str()),
+        Subcommand::Trash { subcommand } => match subcommand {
+            TrashSubcommand::List => command::trash_list(app),
+            TrashSubcommand::Purge { all, id_like } => {
+                command::trash_purge(app, all, id_like.as_deref())
+            }
+            TrashSubcommand::Restore { id_like } => command::trash_restore(app, id_like.as_str()),
+        },
     }
 }
@@ -1,5 +1,12 @@
 use askama::Template;
 
+#[derive(Template)]
+#[template(path = "error.html")]
+pub struct ErrorTemplate<'a> {
+    pub message: &'a str,
+    pub status: &'a str,
+}
+
 #[derive(Template)]
 #[template(path = "index.html")]
 pub struct IndexTemplate {}
@@ -19,11 +26,24 @@ pub struct PageItemTemplate {
 
 pub struct PageWithTitle {
     pub id: String,
+    // the lines that contain the links
+    pub lines: Vec<String>,
     pub obsoleted: bool,
     pub url: String,
     pub title: String,
 }
 
+// the form to create or edit a page
+#[derive(Template)]
+#[template(path = "page-form.html")]
+pub struct PageFormTemplate<'a> {
+    pub action: &'a str,
+    pub cancel_url: &'a str,
+    pub content: &'a str,
+    pub etag: &'a str,
+    pub heading: &'a str,
+}
+
 #[derive(Template)]
 #[template(path = "page.html")]
 pub struct PageTemplate<'a> {
@@ -34,6 +54,39 @@ pub struct PageTemplate<'a> {
     pub title_url: &'a str,
     pub html: String,
     pub obsoleted_by: &'a [PageItemTemplate],
+    pub obsoleted_links: &'a [PageItemTemplate],
+    pub page_linked_by: &'a [PageWithTitle],
+    pub unlinked_mentions: &'a [PageWithTitle],
+}
+
+#[derive(Template)]
+#[template(path = "search.html")]
+pub struct SearchTemplate<'a> {
+    pub all_url: &'a str,
+    pub error: &'a str,
+    pub hits: &'a [SearchItemTemplate],
+    pub no_obsoleted_url: &'a str,
+    pub query: &'a str,
+    pub show_all: bool,
+}
+
+pub struct SearchItemTemplate {
+    pub id: String,
+    pub obsoleted: bool,
+    pub snippets: Vec<SearchSnippetTemplate>,
+    pub title: String,
+    pub url: String,
+}
+
+pub struct SearchSnippetTemplate {
+    pub fragments: Vec<SnippetFragmentTemplate>,
+    pub line_number: usize,
+    pub url: String,
+}
+
+pub struct SnippetFragmentTemplate {
+    pub highlighted: bool,
+    pub text: String,
 }
 
 #[derive(Template)]
@@ -69,6 +122,22 @@ pub struct TitlePagesTemplate<'a> {
 #[derive(Template)]
 #[template(path = "title-not-found.html")]
 pub struct TitleNotFoundTemplate<'a> {
+    pub new_url: &'a str,
+    pub templates: &'a [String],
     pub title: &'a str,
     pub title_url: &'a str,
 }
+
+#[derive(Template)]
+#[template(path = "wanted.html")]
+pub struct WantedTemplate<'a> {
+    pub show_all: bool,
+    pub title: &'a str,
+    pub titles: &'a [WantedItemTemplate],
+}
+
+pub struct WantedItemTemplate {
+    pub pages: Vec<PageItemTemplate>,
+    pub title: String,
+    pub url: String,
+}
@@ -5,3 +5,7 @@ pub fn pages_url() -> String {
 pub fn titles_url() -> String {
     "/titles".to_string()
 }
+
+pub fn wanted_url() -> String {
+    "/wanted".to_string()
+}
@@ -1,25 +1,43 @@
 mod column_number;
+mod daily_title_format;
 mod line_number;
 mod page;
 mod page_content;
+mod page_content_template;
 mod page_graph;
 mod page_id;
 mod page_id_or_page_title;
 mod page_link;
+mod page_link_kind;
+mod page_link_style;
 mod page_path;
 mod page_title;
 mod query;
+mod query_match;
+mod search_hit;
+mod snippet;
+mod text_matcher;
+mod text_span;
 mod title_path;
 
 pub use self::column_number::*;
+pub use self::daily_title_format::*;
 pub use self::line_number::*;
 pub use self::page::*;
 pub use self::page_content::*;
+pub use self::page_content_template::*;
 pub use self::page_graph::*;
 pub use self::page_id::*;
 pub use self::page_id_or_page_title::*;
 pub use self::page_link::*;
+pub use self::page_link_kind::*;
+pub use self::page_link_style::*;
 pub use self::page_path::*;
 pub use self::page_title::*;
 pub use self::query::*;
+pub use self::query_match::*;
+pub use self::search_hit::*;
+pub use self::snippet::*;
+pub use self::text_matcher::*;
+pub use self::text_span::*;
 pub use self::title_path::*;
@@ -0,0 +1,100 @@
+use std::fmt::Write as _;
+
+use chrono::{Duration, NaiveDate};
+use thiserror::Error;
+
+use crate::{PageContent, PageTitle, TitlePath};
+
+#[derive(Debug, Error)]
+#[error("parse daily title format error")]
+pub struct ParseDailyTitleFormatError;
+
+// the strftime format of the titles of daily pages (e.g. "%Y-%m-%d")
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailyTitleFormat(String);
+
+impl DailyTitleFormat {
+    // the content of a new daily page with links to the previous and next days
+    pub fn content(&self, date: NaiveDate) -> PageContent {
+        let link = |date: NaiveDate| {
+            let title = self.title(date);
+            format!("[{}]({})", title, TitlePath::from(title.clone()))
+        };
+        PageContent::from(format!(
+            "# {}\n\n- prev: {}\n- next: {}\n",
+            self.title(date),
+            link(date - Duration::days(1)),
+            link(date + Duration::days(1)),
+        ))
+    }
+
+    pub fn title(&self, date: NaiveDate) -> PageTitle {
+        PageTitle::from(date.format(self.0.as_str()).to_string())
+    }
+}
+
+impl Default for DailyTitleFormat {
+    fn default() -> Self {
+        Self("%Y-%m-%d".to_string())
+    }
+}
+
+impl std::str::FromStr for DailyTitleFormat {
+    type Err = ParseDailyTitleFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseDailyTitleFormatError);
+        }
+        // `to_string()` panics on an invalid item or an item that a date can't render (e.g. "%H")
+        let mut sample = String::new();
+        write!(sample, "{}", NaiveDate::from_ymd(2021, 1, 1).format(s))
+            .map_err(|_| ParseDailyTitleFormatError)?;
+        Ok(Self(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn content_test() {
+        let format = DailyTitleFormat::default();
+        let date = NaiveDate::from_ymd(2021, 3, 1);
+        assert_eq!(
+            format.content(date).to_string(),
+            vec![
+                "# 2021-03-01",
+                "",
+                "- prev: [2021-02-28](/titles/2021%2D02%2D28)",
+                "- next: [2021-03-02](/titles/2021%2D03%2D02)",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn title_test() -> anyhow::Result<()> {
+        let date = NaiveDate::from_ymd(2021, 2, 3);
+        assert_eq!(
+            DailyTitleFormat::default().title(date).as_str(),
+            "2021-02-03"
+        );
+        assert_eq!(
+            DailyTitleFormat::from_str("%Y/%m/%d (%a)")?
+                .title(date)
+                .as_str(),
+            "2021/02/03 (Wed)"
+        );
+        assert!(DailyTitleFormat::from_str("%Q").is_err());
+        // the date has no time and no zone
+        assert!(DailyTitleFormat::from_str("%Y-%m-%d %H").is_err());
+        assert!(DailyTitleFormat::from_str("%Y-%m-%d %z").is_err());
+        assert!(DailyTitleFormat::from_str("").is_err());
+        Ok(())
+    }
+}
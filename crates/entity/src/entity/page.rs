@@ -25,6 +25,17 @@ impl Page {
         Self { id, content }
     }
 
+    pub fn links(&self) -> Vec<PageLink> {
+        self.content.links(self.id)
+    }
+
+    pub fn page_links(&self) -> Vec<PageLink> {
+        self.links()
+            .into_iter()
+            .filter(|page_link| matches!(page_link.to(), PageLinkTo::Page(_)))
+            .collect::<Vec<PageLink>>()
+    }
+
     pub fn title(&self) -> PageTitle {
         self.content.title()
     }
@@ -33,10 +44,9 @@ impl Page {
     // TODO: pub fn rev_title_links(&self) -> BTreeSet<PageId>; // PageTitle -> PageGraph -> BTreeSet<PageId>
 
     pub fn title_links(&self) -> Vec<PageLink> {
-        self.content
-            .title_links()
+        self.links()
             .into_iter()
-            .map(|page_title| PageLink::new(self.id, PageLinkTo::Title(page_title)))
+            .filter(|page_link| matches!(page_link.to(), PageLinkTo::Title(_)))
             .collect::<Vec<PageLink>>()
     }
 }
@@ -45,7 +55,7 @@ impl Page {
 mod tests {
     use std::str::FromStr;
 
-    use crate::{PageContent, PageId, PageTitle};
+    use crate::{PageContent, PageId, PageLinkKind, PageTitle, TextSpan};
 
     use super::*;
 
@@ -75,28 +85,62 @@ mod tests {
     }
 
     #[test]
-    fn title_links_test() -> anyhow::Result<()> {
+    fn page_links_test() -> anyhow::Result<()> {
         let id = PageId::from_str("20210203T040506Z")?;
-        let content = PageContent::from(
-            vec![
-                "# title1",
-                "",
-                "content1",
-                "",
-                "[title1]",
-                "[title2]",
-                "",
-                "[title1]: /titles/title1",
-                "[title2]: /titles/title2",
-            ]
-            .join("\n"),
+        let content = vec![
+            "# title1",
+            "",
+            "[title2](/titles/title2)",
+            "[20210203T040507Z](/pages/20210203T040507Z)",
+        ]
+        .join("\n");
+        let page = Page::new(id, PageContent::from(content.clone()));
+        assert_eq!(
+            page.page_links(),
+            vec![PageLink::new(
+                id,
+                PageLinkTo::Page(PageId::from_str("20210203T040507Z")?),
+                "20210203T040507Z".to_string(),
+                PageLinkKind::Inline,
+                TextSpan::new(content.as_str(), 35..78)
+            )]
         );
-        let page = Page::new(id, content);
+        Ok(())
+    }
+
+    #[test]
+    fn title_links_test() -> anyhow::Result<()> {
+        let id = PageId::from_str("20210203T040506Z")?;
+        let content = vec![
+            "# title1",
+            "",
+            "content1",
+            "",
+            "[title1]",
+            "[title2]",
+            "",
+            "[title1]: /titles/title1",
+            "[title2]: /titles/title2",
+        ]
+        .join("\n");
+        let page = Page::new(id, PageContent::from(content.clone()));
         assert_eq!(
             page.title_links(),
             vec![
-                PageLink::new(id, PageLinkTo::Title(PageTitle::from("title1".to_string()))),
-                PageLink::new(id, PageLinkTo::Title(PageTitle::from("title2".to_string())))
+                PageLink::new(
+                    id,
+                    PageLinkTo::Title(PageTitle::from("title1".to_string())),
+                    "title1".to_string(),
+                    PageLinkKind::Reference,
+                    TextSpan::new(content.as_str(), 20..28)
+                ),
+                PageLink::new(
+                    id,
+                    PageLinkTo::Title(PageTitle::from("title2".to_string())),
+                    "title2".to_string(),
+                    PageLinkKind::Reference,
+                    TextSpan::new(content.as_str(), 29..37)
+                )
             ]
         );
         Ok(())
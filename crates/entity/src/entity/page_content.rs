@@ -1,17 +1,27 @@
-use std::{collections::BTreeSet, str::FromStr};
+use std::{collections::BTreeSet, ops::Range, str::FromStr};
 
-use pulldown_cmark::{BrokenLink, Options, Parser};
+use pulldown_cmark::{BrokenLink, Event, LinkType, Options, Parser, Tag};
 use regex::Regex;
 
-use crate::{PageId, PagePath, PageTitle, TitlePath};
+use crate::{
+    PageId, PageLink, PageLinkKind, PageLinkStyle, PageLinkTo, PagePath, PageTitle, TextSpan,
+    TitlePath,
+};
 
 #[derive(Clone, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
 pub struct PageContent(String);
 
 fn broken_links(content: &str) -> BTreeSet<String> {
+    let wiki_ranges = wiki_links(content)
+        .into_iter()
+        .map(|(range, _, _)| range)
+        .collect::<Vec<Range<usize>>>();
     let mut res = BTreeSet::new();
     let mut callback = |broken_link: BrokenLink| {
-        res.insert(broken_link.reference.to_owned());
+        // `[title]` in `[[title]]` is not a broken link
+        if !is_in(&wiki_ranges, &broken_link.span) {
+            res.insert(broken_link.reference.to_owned());
+        }
         None
     };
     let parser =
@@ -20,7 +30,214 @@ fn broken_links(content: &str) -> BTreeSet<String> {
     res
 }
 
+fn is_in(ranges: &[Range<usize>], range: &Range<usize>) -> bool {
+    ranges
+        .iter()
+        .any(|r| r.start <= range.start && range.end <= r.end)
+}
+
+// (byte range, title, label) of `[[title]]` and `[[title|label]]` (except in code)
+fn wiki_links(content: &str) -> Vec<(Range<usize>, PageTitle, Option<String>)> {
+    let code_ranges = Parser::new(content)
+        .into_offset_iter()
+        .filter_map(|(event, range)| match event {
+            Event::Code(_) | Event::Start(Tag::CodeBlock(_)) => Some(range),
+            _ => None,
+        })
+        .collect::<Vec<Range<usize>>>();
+    let regex = Regex::new(r"\[\[([^\[\]|\n]+)(?:\|([^\[\]\n]+))?\]\]").unwrap();
+    regex
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let m = captures.get(0)?;
+            let title = captures[1].trim();
+            if title.is_empty()
+                || code_ranges
+                    .iter()
+                    .any(|r| r.start < m.end() && m.start() < r.end)
+            {
+                return None;
+            }
+            let label = captures
+                .get(2)
+                .map(|label| label.as_str().trim().to_string());
+            Some((m.range(), PageTitle::from(title.to_string()), label))
+        })
+        .collect()
+}
+
+// (to, label, kind, span)
+fn links(content: &str) -> Vec<(PageLinkTo, String, PageLinkKind, TextSpan)> {
+    let wiki_links = wiki_links(content);
+    let wiki_ranges = wiki_links
+        .iter()
+        .map(|(range, _, _)| range.clone())
+        .collect::<Vec<Range<usize>>>();
+    let obsoletes_index = content.find("\n## Obsoletes").unwrap_or(content.len());
+    // (to, label, kind, byte range)
+    let mut res = vec![];
+    // (to, kind, byte range, label)
+    let mut current: Option<(PageLinkTo, PageLinkKind, Range<usize>, String)> = None;
+    for (event, range) in Parser::new(content).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link(link_type, dest, _)) => {
+                let to = if let Ok(title_path) = TitlePath::from_str(dest.as_ref()) {
+                    PageLinkTo::Title(PageTitle::from(title_path))
+                } else if let Ok(page_path) = PagePath::from_str(dest.as_ref()) {
+                    // the links in `## Obsoletes` are not page links
+                    if range.start >= obsoletes_index {
+                        continue;
+                    }
+                    PageLinkTo::Page(PageId::from(page_path))
+                } else {
+                    continue;
+                };
+                let (kind, range) = match link_type {
+                    LinkType::Autolink | LinkType::Email => (PageLinkKind::Autolink, range),
+                    LinkType::Inline => (PageLinkKind::Inline, range),
+                    // the range of `[label][]` does not include `[]`
+                    LinkType::Collapsed if content[range.end..].starts_with("[]") => {
+                        (PageLinkKind::Reference, range.start..range.end + 2)
+                    }
+                    _ => (PageLinkKind::Reference, range),
+                };
+                current = Some((to, kind, range, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, kind, _, label)) = current.as_mut() {
+                    if *kind != PageLinkKind::Autolink {
+                        label.push_str(text.as_ref());
+                    }
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some((_, _, _, label)) = current.as_mut() {
+                    label.push(' ');
+                }
+            }
+            Event::End(Tag::Link(_, _, _)) => {
+                if let Some((to, kind, range, label)) = current.take() {
+                    // `[title]` in `[[title]]` (with `[title]: url`) is a part of the wiki link
+                    if !is_in(&wiki_ranges, &range) {
+                        res.push((to, label, kind, range));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    for (range, page_title, label) in wiki_links {
+        let label = label.unwrap_or_else(|| page_title.to_string());
+        res.push((
+            PageLinkTo::Title(page_title),
+            label,
+            PageLinkKind::Wiki,
+            range,
+        ));
+    }
+    res.sort_by_key(|(_, _, _, range)| range.start);
+    let spans = TextSpan::new_all(
+        content,
+        res.iter()
+            .map(|(_, _, _, range)| range.clone())
+            .collect::<Vec<Range<usize>>>(),
+    );
+    res.into_iter()
+        .zip(spans)
+        .map(|((to, label, kind, _), span)| (to, label, kind, span))
+        .collect()
+}
+
+// the byte ranges of the title in the text outside links and code (case insensitive)
+fn mentions(content: &str, page_title: &PageTitle) -> Vec<Range<usize>> {
+    if page_title.as_str().is_empty() {
+        return vec![];
+    }
+    let wiki_ranges = wiki_links(content)
+        .into_iter()
+        .map(|(range, _, _)| range)
+        .collect::<Vec<Range<usize>>>();
+    let regex = Regex::new(&format!("(?i){}", regex::escape(page_title.as_str()))).unwrap();
+    // does not match a part of an ASCII word (e.g. `foo` in `foobar`)
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let is_boundary = |title_char: Option<char>, next_char: Option<char>| {
+        !(title_char.map(is_word_char).unwrap_or(false)
+            && next_char.map(is_word_char).unwrap_or(false))
+    };
+
+    let mut res = vec![];
+    let mut find = |text_range: Range<usize>| {
+        for m in regex.find_iter(&content[text_range.clone()]) {
+            let range = text_range.start + m.start()..text_range.start + m.end();
+            if !is_boundary(
+                content[range.clone()].chars().next(),
+                content[..range.start].chars().next_back(),
+            ) || !is_boundary(
+                content[range.clone()].chars().next_back(),
+                content[range.end..].chars().next(),
+            ) {
+                continue;
+            }
+            res.push(range);
+        }
+    };
+    // in links, images or code blocks
+    let mut skip_depth = 0_usize;
+    // the adjacent text events (e.g. `foo`, `_bar`)
+    let mut text_range: Option<Range<usize>> = None;
+    for (event, range) in Parser::new(content).into_offset_iter() {
+        match event {
+            Event::Text(text)
+                if skip_depth == 0
+                    && !is_in(&wiki_ranges, &range)
+                    && &content[range.clone()] == text.as_ref() =>
+            {
+                text_range = match text_range.take() {
+                    Some(r) if r.end == range.start => Some(r.start..range.end),
+                    Some(r) => {
+                        find(r);
+                        Some(range)
+                    }
+                    None => Some(range),
+                };
+                continue;
+            }
+            Event::Start(Tag::Link(_, _, _))
+            | Event::Start(Tag::Image(_, _, _))
+            | Event::Start(Tag::CodeBlock(_)) => skip_depth += 1,
+            Event::End(Tag::Link(_, _, _))
+            | Event::End(Tag::Image(_, _, _))
+            | Event::End(Tag::CodeBlock(_)) => skip_depth = skip_depth.saturating_sub(1),
+            _ => {}
+        }
+        if let Some(r) = text_range.take() {
+            find(r);
+        }
+    }
+    if let Some(r) = text_range.take() {
+        find(r);
+    }
+    res
+}
+
+// the label in the source (e.g. `*foo*` in `[*foo*](url)`)
+fn raw_label(source: &str, kind: PageLinkKind) -> Option<&str> {
+    match kind {
+        PageLinkKind::Inline => source.rfind("](").map(|index| &source[1..index]),
+        PageLinkKind::Reference if source.ends_with("][]") => Some(&source[1..source.len() - 3]),
+        PageLinkKind::Reference => match source.rfind("][") {
+            Some(index) => Some(&source[1..index]),
+            None => Some(&source[1..source.len() - 1]),
+        },
+        PageLinkKind::Autolink | PageLinkKind::Wiki => None,
+    }
+}
+
 impl PageContent {
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
     pub fn broken_links(&self) -> BTreeSet<PageTitle> {
         let links = broken_links(self.0.as_str());
         links
@@ -29,6 +246,88 @@ impl PageContent {
             .collect::<BTreeSet<PageTitle>>()
     }
 
+    pub fn convert_links(&mut self, page_link_style: PageLinkStyle) {
+        match page_link_style {
+            PageLinkStyle::Markdown => self.convert_links_to_markdown(),
+            PageLinkStyle::Wiki => self.convert_links_to_wiki(),
+        }
+    }
+
+    // `[[title|label]]` -> `[label](/titles/title)`
+    fn convert_links_to_markdown(&mut self) {
+        let content = self.0.as_str();
+        let mut replacements = vec![];
+        for (range, page_title, label) in wiki_links(content) {
+            let label = label.unwrap_or_else(|| page_title.to_string());
+            let url = TitlePath::from(page_title).to_string();
+            replacements.push((range, format!("[{}]({})", label, url)));
+        }
+        self.replace_ranges(replacements);
+    }
+
+    // `[label](/titles/title)` or `[label][title]` -> `[[title|label]]`
+    fn convert_links_to_wiki(&mut self) {
+        let is_wiki_safe = |s: &str| !s.is_empty() && !s.contains(|c| "[]|\n".contains(c));
+        let content = self.0.as_str();
+        let mut replacements = vec![];
+        for (to, label, kind, span) in links(content) {
+            let page_title = match to {
+                PageLinkTo::Title(page_title) => page_title,
+                PageLinkTo::Page(_) => continue,
+            };
+            let source = &content[span.byte_range()];
+            // keeps the labels with markups (e.g. `[*foo*](/titles/foo)`)
+            if raw_label(source, kind) != Some(label.as_str()) {
+                continue;
+            }
+            if !is_wiki_safe(page_title.as_str()) || !is_wiki_safe(label.as_str()) {
+                continue;
+            }
+            let replaced = if label == page_title.as_str() {
+                format!("[[{}]]", page_title)
+            } else {
+                format!("[[{}|{}]]", page_title, label)
+            };
+            replacements.push((span.byte_range(), replaced));
+        }
+        if replacements.is_empty() {
+            return;
+        }
+        self.replace_ranges(replacements);
+
+        // removes the unused title definitions (e.g. `[title]: /titles/title`)
+        let content = self.0.as_str();
+        let used = links(content)
+            .into_iter()
+            .filter(|(_, _, kind, _)| *kind == PageLinkKind::Reference)
+            .filter_map(|(_, _, _, span)| {
+                let source = &content[span.byte_range()];
+                let reference = match source.rfind("][") {
+                    Some(index) if !source.ends_with("][]") => &source[index + 2..source.len() - 1],
+                    _ => raw_label(source, PageLinkKind::Reference)?,
+                };
+                Some(reference.to_lowercase())
+            })
+            .collect::<BTreeSet<String>>();
+        let regex = Regex::new(r"(?m)^ {0,3}\[([^\]]+)\]:[ \t]*(\S+)[^\n]*\n?").unwrap();
+        let replacements = regex
+            .captures_iter(content)
+            .filter(|captures| {
+                TitlePath::from_str(captures[2].trim_start_matches('<').trim_end_matches('>'))
+                    .is_ok()
+                    && !used.contains(&captures[1].to_lowercase())
+            })
+            .filter_map(|captures| captures.get(0).map(|m| (m.range(), String::new())))
+            .collect::<Vec<(Range<usize>, String)>>();
+        if replacements.is_empty() {
+            return;
+        }
+        self.replace_ranges(replacements);
+        let trimmed_len = self.0.trim_end().len();
+        self.0.truncate(trimmed_len);
+        self.0.push('\n');
+    }
+
     pub fn ensure_links(&mut self) {
         let links = broken_links(self.0.as_str());
         if links.is_empty() {
@@ -50,6 +349,30 @@ impl PageContent {
         self.0.push('\n');
     }
 
+    pub fn links(&self, from: PageId) -> Vec<PageLink> {
+        links(self.0.as_str())
+            .into_iter()
+            .map(|(to, label, kind, span)| PageLink::new(from, to, label, kind, span))
+            .collect::<Vec<PageLink>>()
+    }
+
+    // replaces the mentions of the title with the links to it
+    pub fn link_mentions(&mut self, page_title: &PageTitle) {
+        let url = TitlePath::from(page_title.clone()).to_string();
+        let replacements = mentions(self.0.as_str(), page_title)
+            .into_iter()
+            .map(|range| {
+                let replaced = format!("[{}]({})", &self.0[range.clone()], url);
+                (range, replaced)
+            })
+            .collect::<Vec<(Range<usize>, String)>>();
+        self.replace_ranges(replacements);
+    }
+
+    pub fn mentions(&self, page_title: &PageTitle) -> Vec<TextSpan> {
+        TextSpan::new_all(self.0.as_str(), mentions(self.0.as_str(), page_title))
+    }
+
     pub fn obsoletes(&self) -> Vec<PageId> {
         self.0
             .find("\n## Obsoletes")
@@ -69,6 +392,17 @@ impl PageContent {
             .unwrap_or_default()
     }
 
+    // the links to `/pages/{id}` (the `## Obsoletes` section is excluded)
+    pub fn page_links(&self) -> Vec<PageId> {
+        links(self.0.as_str())
+            .into_iter()
+            .filter_map(|(to, _, _, _)| match to {
+                PageLinkTo::Page(page_id) => Some(page_id),
+                PageLinkTo::Title(_) => None,
+            })
+            .collect::<Vec<PageId>>()
+    }
+
     pub fn replace_obsoletes(&mut self, page_id: PageId) {
         if let Some(index) = self.0.find("\n## Obsoletes") {
             self.0.truncate(index);
@@ -80,6 +414,131 @@ impl PageContent {
         ));
     }
 
+    // replaces the first line (`# title`)
+    pub fn replace_title(&mut self, page_title: &PageTitle) {
+        let rest = self
+            .0
+            .find('\n')
+            .map(|index| self.0[index..].to_string())
+            .unwrap_or_default();
+        self.0 = format!("# {}{}", page_title, rest);
+    }
+
+    // replaces the links to `from` (and the labels equal to `from`) with the links to `to`
+    pub fn replace_title_links(&mut self, from: &PageTitle, to: &PageTitle) {
+        let from_path = TitlePath::from(from.clone());
+        let to_url = TitlePath::from(to.clone()).to_string();
+        let links_from = |dest: &str| {
+            let dest = dest.trim_start_matches('<').trim_end_matches('>');
+            TitlePath::from_str(dest)
+                .map(|title_path| title_path == from_path)
+                .unwrap_or(false)
+        };
+        let label = |s: &str| -> String {
+            if s == from.as_str() {
+                to.to_string()
+            } else {
+                s.to_string()
+            }
+        };
+
+        let mut replacements: Vec<(Range<usize>, String)> = vec![];
+        // [[title|label]]
+        let wiki_links = wiki_links(self.0.as_str());
+        let wiki_ranges = wiki_links
+            .iter()
+            .map(|(range, _, _)| range.clone())
+            .collect::<Vec<Range<usize>>>();
+        for (range, page_title, label) in wiki_links {
+            if &page_title != from {
+                continue;
+            }
+            let replaced = match label {
+                Some(label) => format!("[[{}|{}]]", to, label),
+                None => format!("[[{}]]", to),
+            };
+            replacements.push((range, replaced));
+        }
+
+        for (event, range) in Parser::new(self.0.as_str()).into_offset_iter() {
+            if is_in(&wiki_ranges, &range) {
+                continue;
+            }
+            let link_type = match event {
+                Event::Start(Tag::Link(link_type, dest, _)) if links_from(dest.as_ref()) => {
+                    link_type
+                }
+                _ => continue,
+            };
+            let source = &self.0[range.clone()];
+            let replaced = match link_type {
+                // [label](url "title")
+                LinkType::Inline => match source.rfind("](") {
+                    Some(index) => {
+                        let rest = source[index + 2..source.len() - 1].trim_start();
+                        let dest_len = if rest.starts_with('<') {
+                            rest.find('>').map(|i| i + 1).unwrap_or(rest.len())
+                        } else {
+                            rest.find(char::is_whitespace).unwrap_or(rest.len())
+                        };
+                        format!(
+                            "[{}]({}{})",
+                            label(&source[1..index]),
+                            to_url,
+                            &rest[dest_len..]
+                        )
+                    }
+                    None => continue,
+                },
+                // [label][ref]
+                LinkType::Reference => match source.rfind("][") {
+                    Some(index) => format!(
+                        "[{}][{}]",
+                        label(&source[1..index]),
+                        label(&source[index + 2..source.len() - 1])
+                    ),
+                    None => continue,
+                },
+                // [label] (or `[label]` in `[label][]`)
+                LinkType::Collapsed | LinkType::Shortcut => {
+                    format!("[{}]", label(&source[1..source.len() - 1]))
+                }
+                _ => continue,
+            };
+            replacements.push((range, replaced));
+        }
+
+        // [ref]: url
+        let regex = Regex::new(r"(?m)^( {0,3}\[)([^\]]+)(\]:[ \t]*)(\S+)").unwrap();
+        for captures in regex.captures_iter(self.0.as_str()) {
+            if !links_from(&captures[4]) {
+                continue;
+            }
+            if let Some(m) = captures.get(0) {
+                replacements.push((
+                    m.range(),
+                    format!(
+                        "{}{}{}{}",
+                        &captures[1],
+                        label(&captures[2]),
+                        &captures[3],
+                        to_url
+                    ),
+                ));
+            }
+        }
+
+        self.replace_ranges(replacements);
+    }
+
+    // replaces the non-overlapping ranges
+    fn replace_ranges(&mut self, mut replacements: Vec<(Range<usize>, String)>) {
+        replacements.sort_by_key(|(range, _)| range.start);
+        for (range, replaced) in replacements.into_iter().rev() {
+            self.0.replace_range(range, replaced.as_str());
+        }
+    }
+
     pub fn title(&self) -> PageTitle {
         self.0
             .lines()
@@ -91,16 +550,12 @@ impl PageContent {
     }
 
     pub fn title_links(&self) -> Vec<PageTitle> {
-        pulldown_cmark::Parser::new(self.0.as_str())
-            .filter_map(|event| match event {
-                pulldown_cmark::Event::End(tag) => Some(tag),
-                _ => None,
-            })
-            .filter_map(|tag| match tag {
-                pulldown_cmark::Tag::Link(_, to, _) => Some(to),
-                _ => None,
+        links(self.0.as_str())
+            .into_iter()
+            .filter_map(|(to, _, _, _)| match to {
+                PageLinkTo::Page(_) => None,
+                PageLinkTo::Title(page_title) => Some(page_title),
             })
-            .filter_map(|to| TitlePath::from_str(to.as_ref()).map(PageTitle::from).ok())
             .collect::<Vec<PageTitle>>()
     }
 }
@@ -164,11 +619,57 @@ mod tests {
         assert_eq!(f("[foo]\n\n[bar]"), set(&["foo", "bar"]));
         assert_eq!(f("[foo] [foo]"), set(&["foo"]));
 
+        assert!(f("[[foo]] [[foo|bar]]").is_empty());
+        assert_eq!(f("`[[foo]]` [bar]"), set(&["bar"]));
+
         assert!(f("[foo]()").is_empty());
         assert!(f("[](url)").is_empty());
         assert!(f("[]").is_empty());
     }
 
+    #[test]
+    fn convert_links_test() {
+        let markdown = vec![
+            "# title1",
+            "",
+            "[foo](/titles/foo) [label](/titles/foo) [*em*](/titles/foo) [baz](/pages/20210203T040506Z)",
+            "[bar] [bar][] [label][bar] `[[foo]]`",
+            "",
+            "[bar]: /titles/bar",
+            "[baz]: https://example.com",
+            "",
+        ]
+        .join("\n");
+        let wiki = vec![
+            "# title1",
+            "",
+            "[[foo]] [[foo|label]] [*em*](/titles/foo) [baz](/pages/20210203T040506Z)",
+            "[[bar]] [[bar]] [[bar|label]] `[[foo]]`",
+            "",
+            "[baz]: https://example.com",
+            "",
+        ]
+        .join("\n");
+        let mut page_content = PageContent::from(markdown);
+        page_content.convert_links(PageLinkStyle::Wiki);
+        assert_eq!(page_content.to_string(), wiki);
+
+        page_content.convert_links(PageLinkStyle::Markdown);
+        assert_eq!(
+            page_content.to_string(),
+            vec![
+                "# title1",
+                "",
+                "[foo](/titles/foo) [label](/titles/foo) [*em*](/titles/foo) [baz](/pages/20210203T040506Z)",
+                "[bar](/titles/bar) [bar](/titles/bar) [label](/titles/bar) `[[foo]]`",
+                "",
+                "[baz]: https://example.com",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
     #[test]
     fn ensure_links_test() -> anyhow::Result<()> {
         let mut page_content = PageContent::from(
@@ -221,6 +722,148 @@ mod tests {
         );
     }
 
+    #[test]
+    fn links_test() -> anyhow::Result<()> {
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        let page_content = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "see [*foo* `bar`](/titles/foo) and [baz][] or [qux",
+                "quux][ref] [[baz]] [[foo|bar]]",
+                "",
+                "[baz]: /titles/baz",
+                "[ref]: /pages/20210203T040507Z",
+                "",
+            ]
+            .join("\n"),
+        );
+        let links = page_content.links(page_id);
+        assert_eq!(
+            links
+                .iter()
+                .map(|link| (
+                    link.to().clone(),
+                    link.label().to_string(),
+                    link.kind(),
+                    usize::from(link.span().line_number()),
+                    usize::from(link.span().column_number()),
+                    usize::from(link.span().end_line_number()),
+                    usize::from(link.span().end_column_number()),
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    PageLinkTo::Title(PageTitle::from("foo".to_string())),
+                    "foo bar".to_string(),
+                    PageLinkKind::Inline,
+                    3,
+                    5,
+                    3,
+                    31
+                ),
+                (
+                    PageLinkTo::Title(PageTitle::from("baz".to_string())),
+                    "baz".to_string(),
+                    PageLinkKind::Reference,
+                    3,
+                    36,
+                    3,
+                    43
+                ),
+                (
+                    PageLinkTo::Page(PageId::from_str("20210203T040507Z")?),
+                    "qux quux".to_string(),
+                    PageLinkKind::Reference,
+                    3,
+                    47,
+                    4,
+                    11
+                ),
+                (
+                    PageLinkTo::Title(PageTitle::from("baz".to_string())),
+                    "baz".to_string(),
+                    PageLinkKind::Wiki,
+                    4,
+                    12,
+                    4,
+                    19
+                ),
+                (
+                    PageLinkTo::Title(PageTitle::from("foo".to_string())),
+                    "bar".to_string(),
+                    PageLinkKind::Wiki,
+                    4,
+                    20,
+                    4,
+                    31
+                ),
+            ]
+        );
+        assert!(links.iter().all(|link| link.from() == &page_id));
+        assert_eq!(
+            &page_content.as_str()[links[1].span().byte_range()],
+            "[baz][]"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn mentions_test() {
+        let mut page_content = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "Foo bar, foobar, foo_bar and [foo](/titles/foo).",
+                "[[foo]] `foo` ![foo](foo.png) <https://example.com/foo> *foo*",
+                "",
+                "```",
+                "foo",
+                "```",
+                "",
+            ]
+            .join("\n"),
+        );
+        let page_title = PageTitle::from("foo".to_string());
+        assert_eq!(
+            page_content
+                .mentions(&page_title)
+                .into_iter()
+                .map(|span| (
+                    usize::from(span.line_number()),
+                    usize::from(span.column_number())
+                ))
+                .collect::<Vec<(usize, usize)>>(),
+            vec![(3, 1), (4, 58)]
+        );
+
+        page_content.link_mentions(&page_title);
+        assert_eq!(
+            page_content.to_string(),
+            vec![
+                "# title1",
+                "",
+                "[Foo](/titles/foo) bar, foobar, foo_bar and [foo](/titles/foo).",
+                "[[foo]] `foo` ![foo](foo.png) <https://example.com/foo> *[foo](/titles/foo)*",
+                "",
+                "```",
+                "foo",
+                "```",
+                "",
+            ]
+            .join("\n")
+        );
+        assert!(page_content.mentions(&page_title).is_empty());
+
+        let page_content = PageContent::from("ぼうずやのメモ".to_string());
+        assert_eq!(
+            page_content
+                .mentions(&PageTitle::from("ぼうずや".to_string()))
+                .len(),
+            1
+        );
+    }
+
     #[test]
     fn obsoletes_test() -> anyhow::Result<()> {
         let page_content = PageContent::from(vec!["# title1", "", "content1", ""].join("\n"));
@@ -250,6 +893,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn page_links_test() -> anyhow::Result<()> {
+        let page_content = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "[20210203T040506Z]",
+                "[inline](/pages/20210203T040507Z)",
+                "[title](/titles/20210203T040508Z)",
+                "[invalid](/pages/foo)",
+                "",
+                "## Obsoletes",
+                "",
+                "- [20210203T040509Z](/pages/20210203T040509Z)",
+                "",
+                "[20210203T040506Z]: /pages/20210203T040506Z",
+                "",
+            ]
+            .join("\n"),
+        );
+        assert_eq!(
+            page_content.page_links(),
+            vec![
+                PageId::from_str("20210203T040506Z")?,
+                PageId::from_str("20210203T040507Z")?
+            ]
+        );
+
+        let page_content = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "[20210203T040506Z]",
+                "",
+                "[20210203T040506Z]: /pages/20210203T040506Z",
+            ]
+            .join("\n"),
+        );
+        assert_eq!(
+            page_content.page_links(),
+            vec![PageId::from_str("20210203T040506Z")?]
+        );
+        Ok(())
+    }
+
     #[test]
     fn replace_obsoletes_test() -> anyhow::Result<()> {
         let mut page_content = PageContent::from(vec!["# title1", "", "content1", ""].join("\n"));
@@ -288,6 +976,55 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn replace_title_test() {
+        let mut page_content = PageContent::from(vec!["# title1", "", "content1"].join("\n"));
+        page_content.replace_title(&PageTitle::from("title2".to_string()));
+        assert_eq!(
+            page_content.to_string(),
+            vec!["# title2", "", "content1"].join("\n")
+        );
+    }
+
+    #[test]
+    fn replace_title_links_test() {
+        let mut page_content = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "[foo](/titles/foo) [label](/titles/foo \"title\") [foo](/titles/bar)",
+                "[foo] [foo][] [label][foo] [bar]",
+                "[[foo]] [[foo|label]] [[bar]]",
+                "`[foo](/titles/foo)`",
+                "",
+                "[foo]: /titles/foo",
+                "[bar]: /titles/bar",
+                "",
+            ]
+            .join("\n"),
+        );
+        page_content.replace_title_links(
+            &PageTitle::from("foo".to_string()),
+            &PageTitle::from("ぼうずや".to_string()),
+        );
+        assert_eq!(
+            page_content.to_string(),
+            vec![
+                "# title1",
+                "",
+                "[ぼうずや](/titles/%E3%81%BC%E3%81%86%E3%81%9A%E3%82%84) [label](/titles/%E3%81%BC%E3%81%86%E3%81%9A%E3%82%84 \"title\") [foo](/titles/bar)",
+                "[ぼうずや] [ぼうずや][] [label][ぼうずや] [bar]",
+                "[[ぼうずや]] [[ぼうずや|label]] [[bar]]",
+                "`[foo](/titles/foo)`",
+                "",
+                "[ぼうずや]: /titles/%E3%81%BC%E3%81%86%E3%81%9A%E3%82%84",
+                "[bar]: /titles/bar",
+                "",
+            ]
+            .join("\n"),
+        );
+    }
+
     #[test]
     fn title_test() -> anyhow::Result<()> {
         let page_content = PageContent::from(vec!["# title1", "", "content1"].join("\n"));
@@ -318,6 +1055,7 @@ mod tests {
                 "[collapsed_unknown][]",
                 "[shortcut]",
                 "[shortcut_unknown]",
+                "[[wiki1]] [[wiki2|label]]",
                 "</titles/%E3%81%BC%E3%81%86%E3%81%9A%E3%82%844>",
                 "",
                 "[ref]: /titles/%E3%81%BC%E3%81%86%E3%81%9A%E3%82%841",
@@ -341,7 +1079,9 @@ mod tests {
                 "bouzuya",
                 "ぼうずや1",
                 "ぼうずや2",
-                "ぼうずや3"
+                "ぼうずや3",
+                "wiki1",
+                "wiki2"
             ]
             .iter()
             .map(|s| s.to_string())
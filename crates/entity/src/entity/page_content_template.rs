@@ -0,0 +1,74 @@
+use crate::{PageContent, PageId, PageTitle};
+
+// the content of a new page with `{{date}}`, `{{id}}` and `{{title}}` variables
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PageContentTemplate {
+    name: String,
+    content: String,
+}
+
+impl PageContentTemplate {
+    pub fn new(name: String, content: String) -> Self {
+        Self { name, content }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn render(&self, page_id: &PageId, page_title: &PageTitle) -> PageContent {
+        let content = self
+            .content
+            .replace("{{date}}", &page_id.date().format("%Y-%m-%d").to_string())
+            .replace("{{id}}", &page_id.to_string())
+            .replace("{{title}}", page_title.as_str());
+        PageContent::from(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn render_test() -> anyhow::Result<()> {
+        let template = PageContentTemplate::new(
+            "book".to_string(),
+            vec![
+                "# {{title}}",
+                "",
+                "- author: ",
+                "- isbn: ",
+                "- read: {{date}}",
+                "",
+                "## Rating",
+                "",
+                "[{{id}}](/pages/{{id}})",
+                "",
+            ]
+            .join("\n"),
+        );
+        assert_eq!(template.name(), "book");
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        let page_title = PageTitle::from("title1".to_string());
+        assert_eq!(
+            template.render(&page_id, &page_title).to_string(),
+            vec![
+                "# title1",
+                "",
+                "- author: ",
+                "- isbn: ",
+                "- read: 2021-02-03",
+                "",
+                "## Rating",
+                "",
+                "[20210203T040506Z](/pages/20210203T040506Z)",
+                "",
+            ]
+            .join("\n")
+        );
+        Ok(())
+    }
+}
@@ -1,11 +1,13 @@
 use std::collections::{BTreeMap, BTreeSet};
 
-use crate::{Page, PageId, PageTitle};
+use crate::{Page, PageId, PageLinkTo, PageTitle};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct PageGraph {
     obsolete_links: BTreeMap<PageId, BTreeSet<PageId>>,
+    page_links: BTreeMap<PageId, BTreeSet<PageId>>,
     rev_obsolete_links: BTreeMap<PageId, BTreeSet<PageId>>,
+    rev_page_links: BTreeMap<PageId, BTreeSet<PageId>>,
     titles: BTreeMap<PageId, PageTitle>,
     title_links: BTreeMap<PageId, BTreeSet<PageTitle>>,
     rev_titles: BTreeMap<PageTitle, BTreeSet<PageId>>,
@@ -33,45 +35,65 @@ impl PageGraph {
             .entry(page_title)
             .or_insert_with(BTreeSet::new)
             .insert(page_id);
-        let title_links = page_content.title_links();
-        for linked_page_title in title_links {
-            self.title_links
-                .entry(page_id)
-                .or_insert_with(BTreeSet::new)
-                .insert(linked_page_title.clone());
-            self.rev_title_links
-                .entry(linked_page_title)
-                .or_insert_with(BTreeSet::new)
-                .insert(page_id);
+        // parses the links once (the links in `## Obsoletes` are excluded)
+        for page_link in page_content.links(page_id) {
+            match page_link.to() {
+                PageLinkTo::Page(linked_page_id) => {
+                    self.page_links
+                        .entry(page_id)
+                        .or_insert_with(BTreeSet::new)
+                        .insert(*linked_page_id);
+                    self.rev_page_links
+                        .entry(*linked_page_id)
+                        .or_insert_with(BTreeSet::new)
+                        .insert(page_id);
+                }
+                PageLinkTo::Title(linked_page_title) => {
+                    self.title_links
+                        .entry(page_id)
+                        .or_insert_with(BTreeSet::new)
+                        .insert(linked_page_title.clone());
+                    self.rev_title_links
+                        .entry(linked_page_title.clone())
+                        .or_insert_with(BTreeSet::new)
+                        .insert(page_id);
+                }
+            }
         }
     }
 
     pub fn remove_page(&mut self, page_id: &PageId) {
-        let page_title = self.titles.get(page_id).unwrap(); // TODO: unwrap
+        // removes empty sets so that the graph equals the one built without the page
+        fn remove_value<K: Ord, V: Ord>(map: &mut BTreeMap<K, BTreeSet<V>>, key: &K, value: &V) {
+            if let Some(values) = map.get_mut(key) {
+                values.remove(value);
+                if values.is_empty() {
+                    map.remove(key);
+                }
+            }
+        }
 
-        self.rev_titles.get_mut(page_title).unwrap().remove(page_id); // TODO: unwrap
+        if let Some(page_title) = self.titles.remove(page_id) {
+            remove_value(&mut self.rev_titles, &page_title, page_id);
+        }
 
-        if let Some(title_links) = self.title_links.get(page_id) {
+        if let Some(title_links) = self.title_links.remove(page_id) {
             for title_link in title_links {
-                self.rev_title_links
-                    .get_mut(title_link)
-                    .unwrap()
-                    .remove(page_id); // TODO: unwrap
+                remove_value(&mut self.rev_title_links, &title_link, page_id);
             }
         }
 
-        if let Some(obsolete_links) = self.obsolete_links.get(page_id) {
-            for obsolete_link in obsolete_links {
-                self.rev_obsolete_links
-                    .get_mut(obsolete_link)
-                    .unwrap()
-                    .remove(page_id); // TODo: unwrap
+        if let Some(page_links) = self.page_links.remove(page_id) {
+            for page_link in page_links {
+                remove_value(&mut self.rev_page_links, &page_link, page_id);
             }
         }
 
-        self.obsolete_links.remove(page_id);
-        self.titles.remove(page_id);
-        self.title_links.remove(page_id);
+        if let Some(obsolete_links) = self.obsolete_links.remove(page_id) {
+            for obsolete_link in obsolete_links {
+                remove_value(&mut self.rev_obsolete_links, &obsolete_link, page_id);
+            }
+        }
     }
 
     pub fn is_obsoleted(&self, page_id: &PageId) -> bool {
@@ -95,6 +117,33 @@ impl PageGraph {
             .unwrap_or_default()
     }
 
+    // the pages connected by obsoletion (including the page itself)
+    pub fn obsoletion_chain(&self, page_id: &PageId) -> BTreeSet<PageId> {
+        let mut chain = BTreeSet::new();
+        let mut stack = vec![*page_id];
+        while let Some(page_id) = stack.pop() {
+            if !chain.insert(page_id) {
+                continue;
+            }
+            stack.extend(self.obsoletes(&page_id));
+            stack.extend(self.obsoleted_by(&page_id));
+        }
+        chain
+    }
+
+    // the pages that the page links to (by `/pages/{id}`)
+    pub fn page_links(&self, page_id: &PageId) -> BTreeSet<PageId> {
+        self.page_links.get(page_id).cloned().unwrap_or_default()
+    }
+
+    // the links to the obsoleted versions (the links should be updated)
+    pub fn obsoleted_page_links(&self, page_id: &PageId) -> BTreeSet<PageId> {
+        self.page_links(page_id)
+            .into_iter()
+            .filter(|linked_page_id| self.is_obsoleted(linked_page_id))
+            .collect::<BTreeSet<PageId>>()
+    }
+
     pub fn title(&self, page_id: &PageId) -> Option<PageTitle> {
         self.titles.get(page_id).cloned()
     }
@@ -116,6 +165,22 @@ impl PageGraph {
             .cloned()
             .unwrap_or_default()
     }
+
+    pub fn find_ids_link_to_page(&self, page_id: &PageId) -> BTreeSet<PageId> {
+        self.rev_page_links
+            .get(page_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    // the titles that are linked but no page has (with the pages that link to them)
+    pub fn wanted_titles(&self) -> BTreeMap<PageTitle, BTreeSet<PageId>> {
+        self.rev_title_links
+            .iter()
+            .filter(|(page_title, _)| !self.rev_titles.contains_key(page_title))
+            .map(|(page_title, page_ids)| (page_title.clone(), page_ids.clone()))
+            .collect::<BTreeMap<PageTitle, BTreeSet<PageId>>>()
+    }
 }
 
 #[cfg(test)]
@@ -204,6 +269,89 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn obsoletion_chain_test() -> anyhow::Result<()> {
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let page_id3 = PageId::from_str("20210203T040508Z")?;
+        let page_id4 = PageId::from_str("20210203T040509Z")?;
+        let page_content2 = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "## Obsoletes",
+                "",
+                "- [20210203T040506Z](/pages/20210203T040506Z)",
+                "",
+            ]
+            .join("\n"),
+        );
+        let page_content3 = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "## Obsoletes",
+                "",
+                "- [20210203T040507Z](/pages/20210203T040507Z)",
+                "",
+            ]
+            .join("\n"),
+        );
+        let page_content4 = PageContent::from("# title4".to_string());
+
+        let mut page_graph = PageGraph::default();
+        page_graph.add_page(Page::new(page_id2, page_content2));
+        page_graph.add_page(Page::new(page_id3, page_content3));
+        page_graph.add_page(Page::new(page_id4, page_content4));
+        let chain = vec![page_id1, page_id2, page_id3]
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        assert_eq!(page_graph.obsoletion_chain(&page_id1), chain);
+        assert_eq!(page_graph.obsoletion_chain(&page_id2), chain);
+        assert_eq!(page_graph.obsoletion_chain(&page_id3), chain);
+        assert_eq!(
+            page_graph.obsoletion_chain(&page_id4),
+            vec![page_id4].into_iter().collect::<BTreeSet<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn remove_page_test() -> anyhow::Result<()> {
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let page_content1 = PageContent::from("# title1\n[](/titles/title2)".to_string());
+        let page_content2 = PageContent::from(
+            vec![
+                "# title2",
+                "",
+                "[title1]",
+                "",
+                "## Obsoletes",
+                "",
+                "- [20210203T040506Z](/pages/20210203T040506Z)",
+                "",
+            ]
+            .join("\n"),
+        );
+
+        let mut expected = PageGraph::default();
+        expected.add_page(Page::new(page_id1, page_content1.clone()));
+
+        let mut page_graph = PageGraph::default();
+        page_graph.add_page(Page::new(page_id1, page_content1));
+        page_graph.add_page(Page::new(page_id2, page_content2));
+        page_graph.remove_page(&page_id2);
+        assert_eq!(page_graph, expected);
+
+        page_graph.remove_page(&page_id1);
+        assert_eq!(page_graph, PageGraph::default());
+
+        // does nothing
+        page_graph.remove_page(&page_id1);
+        Ok(())
+    }
+
     #[test]
     fn titled_test() -> anyhow::Result<()> {
         let page_id1 = PageId::from_str("20210203T040506Z")?;
@@ -287,9 +435,12 @@ mod tests {
         let page_id1 = PageId::from_str("20210203T040506Z")?;
         let page_id2 = PageId::from_str("20210203T040507Z")?;
         let page_id3 = PageId::from_str("20210203T040508Z")?;
+        let page_id4 = PageId::from_str("20210203T040509Z")?;
         let page_content1 = PageContent::from("# title1\n[](/titles/title3)".to_string());
         let page_content2 = PageContent::from("# title1".to_string());
         let page_content3 = PageContent::from("# title3\n[](/titles/title1)".to_string());
+        // `[[title3]]` is a title link without `[title3]: /titles/title3`
+        let page_content4 = PageContent::from("# title4\n[[title3]]".to_string());
         let page_title1 = PageTitle::from("title1".to_string());
         let page_title3 = PageTitle::from("title3".to_string());
 
@@ -301,20 +452,128 @@ mod tests {
         page_graph.add_page(Page::new(page_id1, page_content1));
         page_graph.add_page(Page::new(page_id2, page_content2));
         page_graph.add_page(Page::new(page_id3, page_content3));
+        page_graph.add_page(Page::new(page_id4, page_content4));
         assert_eq!(
             page_graph.find_ids_link_to(&page_title1),
             vec![page_id3].into_iter().collect::<BTreeSet<_>>()
         );
         assert_eq!(
             page_graph.find_ids_link_to(&page_title3),
-            vec![page_id1].into_iter().collect::<BTreeSet<_>>()
+            vec![page_id1, page_id4]
+                .into_iter()
+                .collect::<BTreeSet<_>>()
         );
 
         page_graph.remove_page(&page_id1);
         page_graph.remove_page(&page_id2);
         page_graph.remove_page(&page_id3);
+        page_graph.remove_page(&page_id4);
         assert!(page_graph.find_ids_link_to(&page_title1).is_empty());
         assert!(page_graph.find_ids_link_to(&page_title3).is_empty());
         Ok(())
     }
+
+    #[test]
+    fn find_ids_link_to_page_test() -> anyhow::Result<()> {
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let page_id3 = PageId::from_str("20210203T040508Z")?;
+        let page_content2 = PageContent::from(
+            vec![
+                "# title1",
+                "",
+                "## Obsoletes",
+                "",
+                "- [20210203T040506Z](/pages/20210203T040506Z)",
+                "",
+            ]
+            .join("\n"),
+        );
+        let page_content3 = PageContent::from(
+            vec![
+                "# title3",
+                "",
+                "[old](/pages/20210203T040506Z) [new](/pages/20210203T040507Z)",
+                "",
+            ]
+            .join("\n"),
+        );
+
+        let mut page_graph = PageGraph::default();
+        page_graph.add_page(Page::new(page_id2, page_content2));
+        page_graph.add_page(Page::new(page_id3, page_content3));
+        // `## Obsoletes` is not a page link
+        assert!(page_graph.page_links(&page_id2).is_empty());
+        assert_eq!(
+            page_graph.page_links(&page_id3),
+            vec![page_id1, page_id2]
+                .into_iter()
+                .collect::<BTreeSet<_>>()
+        );
+        assert_eq!(
+            page_graph.obsoleted_page_links(&page_id3),
+            vec![page_id1].into_iter().collect::<BTreeSet<_>>()
+        );
+        assert_eq!(
+            page_graph.find_ids_link_to_page(&page_id1),
+            vec![page_id3].into_iter().collect::<BTreeSet<_>>()
+        );
+        assert_eq!(
+            page_graph.find_ids_link_to_page(&page_id2),
+            vec![page_id3].into_iter().collect::<BTreeSet<_>>()
+        );
+        assert!(page_graph.find_ids_link_to_page(&page_id3).is_empty());
+
+        page_graph.remove_page(&page_id3);
+        assert!(page_graph.find_ids_link_to_page(&page_id1).is_empty());
+        assert!(page_graph.find_ids_link_to_page(&page_id2).is_empty());
+        page_graph.remove_page(&page_id2);
+        assert_eq!(page_graph, PageGraph::default());
+        Ok(())
+    }
+
+    #[test]
+    fn wanted_titles_test() -> anyhow::Result<()> {
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let page_content1 = PageContent::from("# title1\n[[title2]] [[title3]]".to_string());
+        let page_content2 = PageContent::from("# title2\n[[title1]] [[title3]]".to_string());
+        let page_title3 = PageTitle::from("title3".to_string());
+
+        let mut page_graph = PageGraph::default();
+        page_graph.add_page(Page::new(page_id1, page_content1));
+        assert_eq!(
+            page_graph.wanted_titles(),
+            vec![
+                (
+                    PageTitle::from("title2".to_string()),
+                    vec![page_id1].into_iter().collect::<BTreeSet<_>>()
+                ),
+                (
+                    page_title3.clone(),
+                    vec![page_id1].into_iter().collect::<BTreeSet<_>>()
+                ),
+            ]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+        );
+
+        page_graph.add_page(Page::new(page_id2, page_content2));
+        assert_eq!(
+            page_graph.wanted_titles(),
+            vec![(
+                page_title3,
+                vec![page_id1, page_id2]
+                    .into_iter()
+                    .collect::<BTreeSet<_>>()
+            )]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>()
+        );
+
+        page_graph.remove_page(&page_id1);
+        page_graph.remove_page(&page_id2);
+        assert!(page_graph.wanted_titles().is_empty());
+        Ok(())
+    }
 }
@@ -15,6 +15,10 @@ impl PageId {
         Self::from_timestamp(Utc::now().timestamp())
     }
 
+    pub fn date(&self) -> NaiveDate {
+        NaiveDateTime::from_timestamp(self.0, 0).date()
+    }
+
     // "YYYYMMDDTHHMMSSZ"
     // "YYYYMMDDTHHMMSSZ.md"
     // "http://localhost:3000/pages/YYYYMMDDTHHMMSSZ"
@@ -33,6 +37,11 @@ impl PageId {
             None
         }
     }
+
+    // one second later
+    pub fn next(&self) -> Option<Self> {
+        Self::from_timestamp(self.0 + 1)
+    }
 }
 
 impl std::fmt::Display for PageId {
@@ -70,6 +79,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn date_test() -> anyhow::Result<()> {
+        let page_id = PageId::from_str("20200808T235959Z")?;
+        assert_eq!(page_id.date(), NaiveDate::from_ymd(2020, 8, 8));
+        Ok(())
+    }
+
     #[test]
     fn from_test() -> anyhow::Result<()> {
         let s = "20200808T002147Z";
@@ -97,4 +113,13 @@ mod tests {
         assert_eq!(from_s, like3);
         Ok(())
     }
+    #[test]
+    fn next_test() -> anyhow::Result<()> {
+        assert_eq!(
+            PageId::from_str("20200808T235959Z")?.next(),
+            Some(PageId::from_str("20200809T000000Z")?)
+        );
+        assert_eq!(PageId::from_str("29991231T235959Z")?.next(), None);
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use thiserror::Error;
 
-use crate::{PageId, PageTitle};
+use crate::{PageId, PageLinkKind, PageTitle, TextSpan};
 
 #[derive(Debug, Error)]
 #[error("parse page path error")]
@@ -8,7 +8,7 @@ pub struct ParsePagePathError;
 
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub enum PageLinkTo {
-    // Page(PageId),
+    Page(PageId),
     Title(PageTitle),
     // TODO: Others
 }
@@ -16,19 +16,47 @@ pub enum PageLinkTo {
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct PageLink {
     from: PageId,
-    // TODO: label: String,
     to: PageLinkTo,
+    // the text of `[label]` (empty for autolinks)
+    label: String,
+    kind: PageLinkKind,
+    // the span of the link in the content
+    span: TextSpan,
 }
 
 impl PageLink {
-    pub fn new(from: PageId, to: PageLinkTo) -> Self {
-        Self { from, to }
+    pub fn new(
+        from: PageId,
+        to: PageLinkTo,
+        label: String,
+        kind: PageLinkKind,
+        span: TextSpan,
+    ) -> Self {
+        Self {
+            from,
+            to,
+            label,
+            kind,
+            span,
+        }
     }
 
     pub fn from(&self) -> &PageId {
         &self.from
     }
 
+    pub fn kind(&self) -> PageLinkKind {
+        self.kind
+    }
+
+    pub fn label(&self) -> &str {
+        self.label.as_str()
+    }
+
+    pub fn span(&self) -> &TextSpan {
+        &self.span
+    }
+
     pub fn to(&self) -> &PageLinkTo {
         &self.to
     }
@@ -45,9 +73,29 @@ mod tests {
         let page_id = PageId::from_str("20210203T040506Z")?;
         let page_title = PageTitle::from("title1".to_string());
         let page_link_to = PageLinkTo::Title(page_title);
-        let page_link = PageLink::new(page_id, page_link_to.clone());
+        let span = TextSpan::new("[label](/titles/title1)", 0..23);
+        let page_link = PageLink::new(
+            page_id,
+            page_link_to.clone(),
+            "label".to_string(),
+            PageLinkKind::Inline,
+            span.clone(),
+        );
         assert_eq!(page_link.from(), &page_id);
         assert_eq!(page_link.to(), &page_link_to);
+        assert_eq!(page_link.label(), "label");
+        assert_eq!(page_link.kind(), PageLinkKind::Inline);
+        assert_eq!(page_link.span(), &span);
+
+        let page_link_to = PageLinkTo::Page(PageId::from_str("20210203T040507Z")?);
+        let page_link = PageLink::new(
+            page_id,
+            page_link_to.clone(),
+            "label".to_string(),
+            PageLinkKind::Inline,
+            span,
+        );
+        assert_eq!(page_link.to(), &page_link_to);
         Ok(())
     }
 }
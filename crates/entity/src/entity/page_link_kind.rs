@@ -0,0 +1,36 @@
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum PageLinkKind {
+    // `<url>`
+    Autolink,
+    // `[label](url)`
+    Inline,
+    // `[label][ref]`, `[label][]` or `[label]` (with `[ref]: url`)
+    Reference,
+    // `[[title]]` or `[[title|label]]`
+    Wiki,
+}
+
+impl std::fmt::Display for PageLinkKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            PageLinkKind::Autolink => "autolink",
+            PageLinkKind::Inline => "inline",
+            PageLinkKind::Reference => "reference",
+            PageLinkKind::Wiki => "wiki",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_test() {
+        assert_eq!(PageLinkKind::Autolink.to_string(), "autolink");
+        assert_eq!(PageLinkKind::Inline.to_string(), "inline");
+        assert_eq!(PageLinkKind::Reference.to_string(), "reference");
+        assert_eq!(PageLinkKind::Wiki.to_string(), "wiki");
+    }
+}
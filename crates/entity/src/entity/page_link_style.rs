@@ -0,0 +1,56 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("parse page link style error")]
+pub struct ParsePageLinkStyleError;
+
+// the syntax of the title links
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PageLinkStyle {
+    // `[title](/titles/title)` or `[title]` with `[title]: /titles/title`
+    Markdown,
+    // `[[title]]`
+    Wiki,
+}
+
+impl std::fmt::Display for PageLinkStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            PageLinkStyle::Markdown => "markdown",
+            PageLinkStyle::Wiki => "wiki",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for PageLinkStyle {
+    type Err = ParsePageLinkStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "markdown" => Ok(PageLinkStyle::Markdown),
+            "wiki" => Ok(PageLinkStyle::Wiki),
+            _ => Err(ParsePageLinkStyleError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn str_conversion_test() -> anyhow::Result<()> {
+        assert_eq!(
+            PageLinkStyle::from_str("markdown")?,
+            PageLinkStyle::Markdown
+        );
+        assert_eq!(PageLinkStyle::from_str("wiki")?, PageLinkStyle::Wiki);
+        assert!(PageLinkStyle::from_str("Wiki").is_err());
+        assert_eq!(PageLinkStyle::Markdown.to_string(), "markdown");
+        assert_eq!(PageLinkStyle::Wiki.to_string(), "wiki");
+        Ok(())
+    }
+}
@@ -1,35 +1,177 @@
-use std::convert::TryFrom;
+use std::{convert::TryFrom, iter::Peekable, ops::Range};
 
+use chrono::NaiveDate;
 use thiserror::Error;
 
-use crate::{ColumnNumber, LineNumber};
+use crate::{
+    ColumnNumber, LineNumber, Page, PageGraph, PageTitle, QueryMatch, QueryOptions, TextMatcher,
+};
 
 #[derive(Debug, Error)]
-#[error("parse query error")]
-pub struct ParseQueryError;
+#[error("parse query error at column {position}: {message}")]
+pub struct ParseQueryError {
+    message: String,
+    position: ColumnNumber,
+}
+
+impl ParseQueryError {
+    // position: the char offset in the query
+    fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: ColumnNumber::try_from(position + 1).expect("invalid column"),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        self.message.as_str()
+    }
+
+    pub fn position(&self) -> ColumnNumber {
+        self.position
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ge,
+    Gt,
+    Le,
+    Lt,
+}
+
+impl Comparison {
+    fn test<T: Ord>(&self, lhs: &T, rhs: &T) -> bool {
+        match self {
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ge => lhs >= rhs,
+            Comparison::Gt => lhs > rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Lt => lhs < rhs,
+        }
+    }
+}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub struct Query(String);
+pub enum QueryExpr {
+    And(Vec<QueryExpr>),
+    Or(Vec<QueryExpr>),
+    Not(Box<QueryExpr>),
+    // "word" or "\"exact phrase\""
+    Text(TextMatcher),
+    // "title:foo"
+    Title(TextMatcher),
+    // "links-to:foo"
+    LinksTo(PageTitle),
+    // "obsoleted:true"
+    Obsoleted(bool),
+    // "created:>=2021-01-01"
+    Created(Comparison, NaiveDate),
+}
+
+impl QueryExpr {
+    pub fn is_match(&self, page: &Page, page_graph: &PageGraph) -> bool {
+        match self {
+            QueryExpr::And(exprs) => exprs.iter().all(|e| e.is_match(page, page_graph)),
+            QueryExpr::Or(exprs) => exprs.iter().any(|e| e.is_match(page, page_graph)),
+            QueryExpr::Not(expr) => !expr.is_match(page, page_graph),
+            QueryExpr::Text(matcher) => matcher.is_match(page.content().as_str()),
+            QueryExpr::Title(matcher) => matcher.is_match(page.title().as_str()),
+            QueryExpr::LinksTo(page_title) => {
+                page_graph.find_ids_link_to(page_title).contains(page.id())
+            }
+            QueryExpr::Obsoleted(b) => page_graph.is_obsoleted(page.id()) == *b,
+            QueryExpr::Created(comparison, date) => comparison.test(&page.id().date(), date),
+        }
+    }
+
+    pub fn uses_page_graph(&self) -> bool {
+        match self {
+            QueryExpr::And(exprs) | QueryExpr::Or(exprs) => {
+                exprs.iter().any(|expr| expr.uses_page_graph())
+            }
+            QueryExpr::Not(expr) => expr.uses_page_graph(),
+            QueryExpr::LinksTo(_) | QueryExpr::Obsoleted(_) => true,
+            QueryExpr::Text(_) | QueryExpr::Title(_) | QueryExpr::Created(_, _) => false,
+        }
+    }
+
+    fn texts<'a>(&'a self, texts: &mut Vec<&'a TextMatcher>) {
+        match self {
+            QueryExpr::And(exprs) | QueryExpr::Or(exprs) => {
+                for expr in exprs {
+                    expr.texts(texts);
+                }
+            }
+            QueryExpr::Text(matcher) => texts.push(matcher),
+            QueryExpr::Not(_)
+            | QueryExpr::Title(_)
+            | QueryExpr::LinksTo(_)
+            | QueryExpr::Obsoleted(_)
+            | QueryExpr::Created(_, _) => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Query {
+    expr: QueryExpr,
+    source: String,
+}
 
 impl Query {
-    pub fn matches(&self, content: &str) -> Vec<(LineNumber, ColumnNumber)> {
+    pub fn expr(&self) -> &QueryExpr {
+        &self.expr
+    }
+
+    pub fn is_match(&self, page: &Page, page_graph: &PageGraph) -> bool {
+        self.expr.is_match(page, page_graph)
+    }
+
+    // returns every occurrence of the text terms (excluding negated terms)
+    pub fn matches(&self, content: &str) -> Vec<QueryMatch> {
+        let mut texts = vec![];
+        self.expr.texts(&mut texts);
         let mut matches = vec![];
         for (line, line_content) in content.lines().enumerate() {
-            if let Some(col) = line_content.find(self.0.as_str()) {
-                matches.push((
-                    LineNumber::try_from(line + 1).expect("invalid line"),
-                    ColumnNumber::try_from(line_content[..col].chars().count() + 1)
-                        .expect("invalid column"),
-                ));
-            }
+            let line_number = LineNumber::try_from(line + 1).expect("invalid line");
+            let mut ranges = texts
+                .iter()
+                .flat_map(|matcher| matcher.find_iter(line_content))
+                .collect::<Vec<Range<usize>>>();
+            ranges.sort_by_key(|range| (range.start, range.end));
+            ranges.dedup();
+            matches.extend(
+                ranges
+                    .into_iter()
+                    .map(|range| QueryMatch::new(line_number, line_content, range)),
+            );
         }
         matches
     }
+
+    pub fn parse(s: &str, options: QueryOptions) -> Result<Self, ParseQueryError> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            end: s.chars().count(),
+            options,
+            tokens: tokens.into_iter().peekable(),
+        };
+        let expr = parser.parse_or()?;
+        if let Some((position, _)) = parser.tokens.next() {
+            return Err(ParseQueryError::new(position, "unexpected token"));
+        }
+        Ok(Self {
+            expr,
+            source: s.to_string(),
+        })
+    }
 }
 
 impl std::fmt::Display for Query {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.source)
     }
 }
 
@@ -37,7 +179,213 @@ impl std::str::FromStr for Query {
     type Err = ParseQueryError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.to_string()))
+        Self::parse(s, QueryOptions::default())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+enum Token {
+    LParen,
+    Minus,
+    Or,
+    RParen,
+    Term(Option<String>, String),
+}
+
+type Chars<'a> = Peekable<std::iter::Enumerate<std::str::Chars<'a>>>;
+
+fn read_quoted(chars: &mut Chars) -> Result<String, ParseQueryError> {
+    let (start, c) = chars.next().expect("quote");
+    assert_eq!(c, '"');
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            None => return Err(ParseQueryError::new(start, "unterminated quote")),
+            Some((_, '"')) => return Ok(s),
+            Some((_, c)) => s.push(c),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, ParseQueryError> {
+    let mut tokens = vec![];
+    let mut chars = s.chars().enumerate().peekable();
+    while let Some(&(position, c)) = chars.peek() {
+        match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push((position, Token::LParen));
+            }
+            ')' => {
+                chars.next();
+                tokens.push((position, Token::RParen));
+            }
+            '-' => {
+                chars.next();
+                match chars.peek() {
+                    Some(&(_, c)) if !c.is_whitespace() && c != ')' => {
+                        tokens.push((position, Token::Minus))
+                    }
+                    _ => tokens.push((position, Token::Term(None, "-".to_string()))),
+                }
+            }
+            '"' => {
+                let quoted = read_quoted(&mut chars)?;
+                tokens.push((position, Token::Term(None, quoted)));
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(&(_, c)) = chars.peek() {
+                    if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                let field = word
+                    .split_once(':')
+                    .map(|(name, _)| name)
+                    .filter(|name| ["title", "links-to", "obsoleted", "created"].contains(name))
+                    .map(|name| name.to_string());
+                let token = match field {
+                    Some(name) => {
+                        let mut value = word[name.len() + 1..].to_string();
+                        if value.is_empty() && chars.peek().map(|(_, c)| *c) == Some('"') {
+                            value = read_quoted(&mut chars)?;
+                        }
+                        Token::Term(Some(name), value)
+                    }
+                    None if word == "OR" => Token::Or,
+                    None => Token::Term(None, word),
+                };
+                tokens.push((position, token));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    end: usize,
+    options: QueryOptions,
+    tokens: Peekable<std::vec::IntoIter<(usize, Token)>>,
+}
+
+impl Parser {
+    fn peek(&mut self) -> Option<&Token> {
+        self.tokens.peek().map(|(_, token)| token)
+    }
+
+    fn position(&mut self) -> usize {
+        let end = self.end;
+        self.tokens.peek().map(|(p, _)| *p).unwrap_or(end)
+    }
+
+    fn parse_or(&mut self) -> Result<QueryExpr, ParseQueryError> {
+        let mut exprs = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.tokens.next();
+            let position = self.position();
+            match self.parse_and()? {
+                QueryExpr::And(and) if and.is_empty() => {
+                    return Err(ParseQueryError::new(position, "missing term after OR"))
+                }
+                expr => exprs.push(expr),
+            }
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            QueryExpr::Or(exprs)
+        })
+    }
+
+    fn parse_and(&mut self) -> Result<QueryExpr, ParseQueryError> {
+        let mut exprs = vec![];
+        while let Some(token) = self.peek() {
+            match token {
+                Token::Or | Token::RParen => break,
+                _ => exprs.push(self.parse_unary()?),
+            }
+        }
+        if exprs.is_empty() && self.peek().is_some() {
+            let position = self.position();
+            return Err(ParseQueryError::new(position, "missing term"));
+        }
+        Ok(if exprs.len() == 1 {
+            exprs.remove(0)
+        } else {
+            QueryExpr::And(exprs)
+        })
+    }
+
+    fn parse_unary(&mut self) -> Result<QueryExpr, ParseQueryError> {
+        let position = self.position();
+        match self.tokens.next().map(|(_, token)| token) {
+            Some(Token::Minus) => Ok(QueryExpr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.tokens.next() {
+                    Some((_, Token::RParen)) => Ok(expr),
+                    _ => Err(ParseQueryError::new(position, "unclosed parenthesis")),
+                }
+            }
+            Some(Token::Term(None, value)) => self
+                .text_matcher(position, value.as_str())
+                .map(QueryExpr::Text),
+            Some(Token::Term(Some(name), value)) => {
+                self.parse_field(position, name.as_str(), value.as_str())
+            }
+            Some(Token::Or) | Some(Token::RParen) | None => {
+                Err(ParseQueryError::new(position, "missing term"))
+            }
+        }
+    }
+
+    fn parse_field(
+        &self,
+        position: usize,
+        name: &str,
+        value: &str,
+    ) -> Result<QueryExpr, ParseQueryError> {
+        match name {
+            "title" => self.text_matcher(position, value).map(QueryExpr::Title),
+            "links-to" => Ok(QueryExpr::LinksTo(PageTitle::from(value.to_string()))),
+            "obsoleted" => match value {
+                "true" => Ok(QueryExpr::Obsoleted(true)),
+                "false" => Ok(QueryExpr::Obsoleted(false)),
+                _ => Err(ParseQueryError::new(
+                    position,
+                    "obsoleted must be true or false",
+                )),
+            },
+            "created" => {
+                let (comparison, date) = [
+                    (">=", Comparison::Ge),
+                    ("<=", Comparison::Le),
+                    (">", Comparison::Gt),
+                    ("<", Comparison::Lt),
+                    ("=", Comparison::Eq),
+                ]
+                .iter()
+                .find_map(|(prefix, comparison)| {
+                    value.strip_prefix(prefix).map(|date| (*comparison, date))
+                })
+                .unwrap_or((Comparison::Eq, value));
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| ParseQueryError::new(position, "created must be YYYY-MM-DD"))?;
+                Ok(QueryExpr::Created(comparison, date))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn text_matcher(&self, position: usize, pattern: &str) -> Result<TextMatcher, ParseQueryError> {
+        TextMatcher::new(pattern, &self.options)
+            .map_err(|e| ParseQueryError::new(position, format!("invalid pattern: {}", e)))
     }
 }
 
@@ -45,6 +393,8 @@ impl std::str::FromStr for Query {
 mod tests {
     use std::str::FromStr;
 
+    use crate::{PageContent, PageId};
+
     use super::*;
 
     #[test]
@@ -54,21 +404,244 @@ mod tests {
         let c1 = ColumnNumber::try_from(1_usize)?;
         let c2 = ColumnNumber::try_from(2_usize)?;
         let c3 = ColumnNumber::try_from(3_usize)?;
+        let positions = |query: &Query, content: &str| {
+            query
+                .matches(content)
+                .into_iter()
+                .map(|m| (m.line_number(), m.column_number()))
+                .collect::<Vec<(LineNumber, ColumnNumber)>>()
+        };
         let query = Query::from_str("a")?;
         assert!(query.matches("").is_empty());
-        assert_eq!(query.matches("a"), vec![(l1, c1)]);
-        assert_eq!(query.matches("ba"), vec![(l1, c2)]);
-        assert_eq!(query.matches("aba"), vec![(l1, c1)]);
-        assert_eq!(query.matches("\n"), vec![]);
-        assert_eq!(query.matches("a\n"), vec![(l1, c1)]);
-        assert_eq!(query.matches("a\na"), vec![(l1, c1), (l2, c1)]);
-        assert_eq!(query.matches("a\nba"), vec![(l1, c1), (l2, c2)]);
-        assert_eq!(query.matches("\na"), vec![(l2, c1)]);
+        assert_eq!(positions(&query, "a"), vec![(l1, c1)]);
+        assert_eq!(positions(&query, "ba"), vec![(l1, c2)]);
+        assert_eq!(positions(&query, "aba"), vec![(l1, c1), (l1, c3)]);
+        assert_eq!(positions(&query, "\n"), vec![]);
+        assert_eq!(positions(&query, "a\n"), vec![(l1, c1)]);
+        assert_eq!(positions(&query, "a\na"), vec![(l1, c1), (l2, c1)]);
+        assert_eq!(positions(&query, "a\nba"), vec![(l1, c1), (l2, c2)]);
+        assert_eq!(positions(&query, "\na"), vec![(l2, c1)]);
 
         let query = Query::from_str("あ")?;
-        assert_eq!(query.matches("あいうえお"), vec![(l1, c1)]);
+        assert_eq!(positions(&query, "あいうえお"), vec![(l1, c1)]);
         let query = Query::from_str("く")?;
-        assert_eq!(query.matches("あいうえお\nかきくけこ"), vec![(l2, c3)]);
+        assert_eq!(positions(&query, "あいうえお\nかきくけこ"), vec![(l2, c3)]);
+
+        let query = Query::from_str("b a -c title:x")?;
+        assert_eq!(positions(&query, "abc"), vec![(l1, c1), (l1, c2)]);
+
+        let query = Query::from_str("いう")?;
+        assert_eq!(
+            query.matches("あいうえお いう"),
+            vec![
+                QueryMatch::new(l1, "あいうえお いう", 3..9),
+                QueryMatch::new(l1, "あいうえお いう", 16..22),
+            ]
+        );
+        let query_match = &query.matches("あいうえお いう")[1];
+        assert_eq!(query_match.byte_range(), &(16..22));
+        assert_eq!(
+            query_match.column_range(),
+            &(ColumnNumber::try_from(7_usize)?..ColumnNumber::try_from(9_usize)?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_test() -> anyhow::Result<()> {
+        let text = |s: &str| QueryExpr::Text(TextMatcher::Literal(s.to_string()));
+        let parse = |s: &str| Query::from_str(s).map(|q| q.expr().clone());
+        assert_eq!(parse("")?, QueryExpr::And(vec![]));
+        assert_eq!(parse("foo")?, text("foo"));
+        assert_eq!(
+            parse("foo bar")?,
+            QueryExpr::And(vec![text("foo"), text("bar")])
+        );
+        assert_eq!(parse("\"foo bar\"")?, text("foo bar"));
+        assert_eq!(
+            parse("foo OR bar baz")?,
+            QueryExpr::Or(vec![
+                text("foo"),
+                QueryExpr::And(vec![text("bar"), text("baz")])
+            ])
+        );
+        assert_eq!(
+            parse("(foo OR bar) baz")?,
+            QueryExpr::And(vec![
+                QueryExpr::Or(vec![text("foo"), text("bar")]),
+                text("baz")
+            ])
+        );
+        assert_eq!(parse("-foo")?, QueryExpr::Not(Box::new(text("foo"))));
+        assert_eq!(
+            parse("a - b")?,
+            QueryExpr::And(vec![text("a"), text("-"), text("b")])
+        );
+        assert_eq!(
+            parse("title:foo")?,
+            QueryExpr::Title(TextMatcher::Literal("foo".to_string()))
+        );
+        assert_eq!(
+            parse("links-to:\"Book X\"")?,
+            QueryExpr::LinksTo(PageTitle::from("Book X".to_string()))
+        );
+        assert_eq!(parse("obsoleted:false")?, QueryExpr::Obsoleted(false));
+        assert_eq!(
+            parse("created:>=2021-01-01")?,
+            QueryExpr::Created(Comparison::Ge, NaiveDate::from_ymd(2021, 1, 1))
+        );
+        assert_eq!(
+            parse("created:2021-01-01")?,
+            QueryExpr::Created(Comparison::Eq, NaiveDate::from_ymd(2021, 1, 1))
+        );
+        assert_eq!(parse("http://example.com")?, text("http://example.com"));
+
+        assert!(parse("\"foo").is_err());
+        assert!(parse("(foo").is_err());
+        assert!(parse("foo)").is_err());
+        assert!(parse("OR foo").is_err());
+        assert!(parse("foo OR").is_err());
+        assert!(parse("()").is_err());
+        assert!(parse("obsoleted:yes").is_err());
+        assert!(parse("created:2021").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn is_match_test() -> anyhow::Result<()> {
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210204T040506Z")?;
+        let page1 = Page::new(
+            page_id1,
+            PageContent::from(vec!["# title1", "", "foo bar", "", "[title2]", ""].join("\n")),
+        );
+        let page2 = Page::new(
+            page_id2,
+            PageContent::from(
+                vec![
+                    "# title2",
+                    "",
+                    "baz",
+                    "",
+                    "## Obsoletes",
+                    "",
+                    "- [20210203T040506Z](/pages/20210203T040506Z)",
+                ]
+                .join("\n"),
+            ),
+        );
+        let mut page_graph = PageGraph::default();
+        page_graph.add_page(page1.clone());
+        page_graph.add_page(page2.clone());
+
+        let is_match = |s: &str, page: &Page| -> anyhow::Result<bool> {
+            Ok(Query::from_str(s)?.is_match(page, &page_graph))
+        };
+        assert!(is_match("", &page1)?);
+        assert!(is_match("foo", &page1)?);
+        assert!(!is_match("foo", &page2)?);
+        assert!(is_match("\"foo bar\"", &page1)?);
+        assert!(!is_match("\"bar foo\"", &page1)?);
+        assert!(is_match("foo OR baz", &page2)?);
+        assert!(!is_match("foo -bar", &page1)?);
+        assert!(is_match("title:title", &page1)?);
+        assert!(!is_match("title:title2", &page1)?);
+        assert!(is_match("links-to:title2", &page1)?);
+        assert!(!is_match("links-to:title2", &page2)?);
+        assert!(is_match("obsoleted:true", &page1)?);
+        assert!(is_match("obsoleted:false", &page2)?);
+        assert!(is_match("created:2021-02-03", &page1)?);
+        assert!(!is_match("created:>2021-02-03", &page1)?);
+        assert!(is_match("created:>2021-02-03", &page2)?);
+        assert!(is_match(
+            "created:<=2021-02-04 -created:<2021-02-04",
+            &page2
+        )?);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let position = |s: &str, options: QueryOptions| {
+            Query::parse(s, options)
+                .map_err(|e| usize::from(e.position()))
+                .err()
+        };
+        let regex = QueryOptions {
+            regex: true,
+            ..Default::default()
+        };
+        assert_eq!(position("foo", regex), None);
+        assert_eq!(position("foo \"bar", QueryOptions::default()), Some(5));
+        assert_eq!(position("foo (bar", QueryOptions::default()), Some(5));
+        assert_eq!(position("foo bar)", QueryOptions::default()), Some(8));
+        assert_eq!(position("foo OR", QueryOptions::default()), Some(7));
+        assert_eq!(position("a obsoleted:x", QueryOptions::default()), Some(3));
+        assert_eq!(position("foo \"a(\"", QueryOptions::default()), None);
+        assert_eq!(position("foo \"a(\"", regex), Some(5));
+        assert_eq!(position("あ title:[", regex), Some(3));
+        assert!(Query::parse("\"(\"", regex)
+            .unwrap_err()
+            .message()
+            .starts_with("invalid pattern"));
+    }
+
+    #[test]
+    fn parse_options_test() -> anyhow::Result<()> {
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        let page = Page::new(
+            page_id,
+            PageContent::from(vec!["# Title1", "", "Foo bar", "foobar"].join("\n")),
+        );
+        let mut page_graph = PageGraph::default();
+        page_graph.add_page(page.clone());
+        let positions =
+            |s: &str, options: QueryOptions| -> anyhow::Result<Option<Vec<(usize, usize)>>> {
+                let query = Query::parse(s, options)?;
+                Ok(if query.is_match(&page, &page_graph) {
+                    Some(
+                        query
+                            .matches(page.content().as_str())
+                            .into_iter()
+                            .map(|m| (usize::from(m.line_number()), usize::from(m.column_number())))
+                            .collect(),
+                    )
+                } else {
+                    None
+                })
+            };
+        let ignore_case = QueryOptions {
+            ignore_case: true,
+            ..Default::default()
+        };
+        let regex = QueryOptions {
+            regex: true,
+            ..Default::default()
+        };
+        let word = QueryOptions {
+            word: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            positions("foo", QueryOptions::default())?,
+            Some(vec![(4, 1)])
+        );
+        assert_eq!(positions("foo", ignore_case)?, Some(vec![(3, 1), (4, 1)]));
+        assert_eq!(positions("title:title1", QueryOptions::default())?, None);
+        assert_eq!(positions("title:title1", ignore_case)?, Some(vec![]));
+        assert_eq!(positions("o+b", regex)?, Some(vec![(4, 2)]));
+        assert_eq!(positions("\"[Ff]oo \"", regex)?, Some(vec![(3, 1)]));
+        assert_eq!(positions("bar", word)?, Some(vec![(3, 5)]));
+        Ok(())
+    }
+
+    #[test]
+    fn uses_page_graph_test() -> anyhow::Result<()> {
+        let uses_page_graph =
+            |s: &str| -> anyhow::Result<bool> { Ok(Query::from_str(s)?.expr().uses_page_graph()) };
+        assert!(!uses_page_graph("foo title:bar created:2021-01-01")?);
+        assert!(uses_page_graph("foo OR -obsoleted:true")?);
+        assert!(uses_page_graph("(links-to:foo)")?);
         Ok(())
     }
 
@@ -76,6 +649,8 @@ mod tests {
     fn str_conversion_test() -> anyhow::Result<()> {
         let query = Query::from_str("query1")?;
         assert_eq!(query.to_string(), "query1");
+        let query = Query::from_str("title:foo -\"bar baz\"")?;
+        assert_eq!(query.to_string(), "title:foo -\"bar baz\"");
         Ok(())
     }
 }
@@ -0,0 +1,70 @@
+use std::{convert::TryFrom, ops::Range};
+
+use crate::{ColumnNumber, LineNumber};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueryMatch {
+    line_number: LineNumber,
+    // byte offsets in the line
+    byte_range: Range<usize>,
+    // char columns in the line (end exclusive)
+    column_range: Range<ColumnNumber>,
+}
+
+impl QueryMatch {
+    pub fn new(line_number: LineNumber, line: &str, byte_range: Range<usize>) -> Self {
+        let start = line[..byte_range.start].chars().count() + 1;
+        let end = start + line[byte_range.clone()].chars().count();
+        Self {
+            line_number,
+            byte_range,
+            column_range: ColumnNumber::try_from(start).expect("invalid column")
+                ..ColumnNumber::try_from(end).expect("invalid column"),
+        }
+    }
+
+    pub fn byte_range(&self) -> &Range<usize> {
+        &self.byte_range
+    }
+
+    pub fn column_number(&self) -> ColumnNumber {
+        self.column_range.start
+    }
+
+    pub fn column_range(&self) -> &Range<ColumnNumber> {
+        &self.column_range
+    }
+
+    pub fn line_number(&self) -> LineNumber {
+        self.line_number
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let l1 = LineNumber::try_from(1_usize)?;
+        let query_match = QueryMatch::new(l1, "abcde", 1..3);
+        assert_eq!(query_match.line_number(), l1);
+        assert_eq!(query_match.byte_range(), &(1..3));
+        assert_eq!(
+            query_match.column_number(),
+            ColumnNumber::try_from(2_usize)?
+        );
+        assert_eq!(
+            query_match.column_range(),
+            &(ColumnNumber::try_from(2_usize)?..ColumnNumber::try_from(4_usize)?)
+        );
+
+        let query_match = QueryMatch::new(l1, "あいうえお", 3..9);
+        assert_eq!(query_match.byte_range(), &(3..9));
+        assert_eq!(
+            query_match.column_range(),
+            &(ColumnNumber::try_from(2_usize)?..ColumnNumber::try_from(4_usize)?)
+        );
+        Ok(())
+    }
+}
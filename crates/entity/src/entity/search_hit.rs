@@ -0,0 +1,96 @@
+use crate::{PageId, PageTitle, Snippet};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SearchHit {
+    obsoleted: bool,
+    page_id: PageId,
+    score: usize,
+    // one snippet per matched line (in line order)
+    snippets: Vec<Snippet>,
+    title: PageTitle,
+}
+
+impl SearchHit {
+    pub fn new(
+        page_id: PageId,
+        title: PageTitle,
+        obsoleted: bool,
+        score: usize,
+        snippets: Vec<Snippet>,
+    ) -> Self {
+        Self {
+            obsoleted,
+            page_id,
+            score,
+            snippets,
+            title,
+        }
+    }
+
+    pub fn is_obsoleted(&self) -> bool {
+        self.obsoleted
+    }
+
+    pub fn page_id(&self) -> &PageId {
+        &self.page_id
+    }
+
+    pub fn score(&self) -> usize {
+        self.score
+    }
+
+    // the first snippet in the body (or the title line)
+    pub fn snippet(&self) -> Option<&Snippet> {
+        self.snippets
+            .iter()
+            .find(|snippet| usize::from(snippet.line_number()) > 1)
+            .or_else(|| self.snippets.first())
+    }
+
+    pub fn snippets(&self) -> &[Snippet] {
+        self.snippets.as_slice()
+    }
+
+    pub fn title(&self) -> &PageTitle {
+        &self.title
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{convert::TryFrom, str::FromStr};
+
+    use super::*;
+    use crate::LineNumber;
+
+    #[test]
+    fn snippet_test() -> anyhow::Result<()> {
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        let title = PageTitle::from("foo".to_string());
+        let l1 = LineNumber::try_from(1_usize)?;
+        let l3 = LineNumber::try_from(3_usize)?;
+        let title_snippet = Snippet::new(l1, "# foo", &[]);
+        let body_snippet = Snippet::new(l3, "foo", &[]);
+
+        let hit = SearchHit::new(page_id, title.clone(), false, 1, vec![]);
+        assert_eq!(hit.snippet(), None);
+        let hit = SearchHit::new(
+            page_id,
+            title.clone(),
+            false,
+            1,
+            vec![title_snippet.clone()],
+        );
+        assert_eq!(hit.snippet(), Some(&title_snippet));
+        let hit = SearchHit::new(
+            page_id,
+            title,
+            false,
+            2,
+            vec![title_snippet, body_snippet.clone()],
+        );
+        assert_eq!(hit.snippet(), Some(&body_snippet));
+        assert_eq!(hit.snippets().len(), 2);
+        Ok(())
+    }
+}
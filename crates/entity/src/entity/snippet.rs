@@ -0,0 +1,126 @@
+use std::ops::Range;
+
+use crate::LineNumber;
+
+// chars before the first highlight
+const CONTEXT_CHARS: usize = 40;
+const MAX_CHARS: usize = 120;
+const ELLIPSIS: &str = "…";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snippet {
+    line_number: LineNumber,
+    text: String,
+    // sorted, non-overlapping and non-empty byte ranges in the text
+    highlights: Vec<Range<usize>>,
+}
+
+impl Snippet {
+    pub fn new(line_number: LineNumber, line: &str, byte_ranges: &[Range<usize>]) -> Self {
+        let char_offsets = line
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .collect::<Vec<usize>>();
+        let to_byte_offset = |c: usize| char_offsets.get(c).copied().unwrap_or(line.len());
+        let first = byte_ranges.iter().map(|r| r.start).min().unwrap_or(0);
+        let start = line[..first].chars().count().saturating_sub(CONTEXT_CHARS);
+        let end = (start + MAX_CHARS).min(char_offsets.len());
+        let (start, end) = (to_byte_offset(start), to_byte_offset(end));
+
+        let prefix = if start > 0 { ELLIPSIS } else { "" };
+        let suffix = if end < line.len() { ELLIPSIS } else { "" };
+        let text = format!("{}{}{}", prefix, &line[start..end], suffix);
+
+        let mut byte_ranges = byte_ranges.to_vec();
+        byte_ranges.sort_by_key(|r| (r.start, r.end));
+        let mut highlights: Vec<Range<usize>> = vec![];
+        for r in byte_ranges {
+            let (s, e) = (r.start.max(start), r.end.min(end));
+            if s >= e {
+                continue;
+            }
+            let r = s - start + prefix.len()..e - start + prefix.len();
+            match highlights.last_mut() {
+                Some(last) if last.end >= r.start => last.end = last.end.max(r.end),
+                _ => highlights.push(r),
+            }
+        }
+
+        Self {
+            line_number,
+            text,
+            highlights,
+        }
+    }
+
+    // (text, highlighted)
+    pub fn fragments(&self) -> Vec<(&str, bool)> {
+        let mut fragments = vec![];
+        let mut offset = 0;
+        for r in self.highlights.iter() {
+            if offset < r.start {
+                fragments.push((&self.text[offset..r.start], false));
+            }
+            fragments.push((&self.text[r.clone()], true));
+            offset = r.end;
+        }
+        if offset < self.text.len() {
+            fragments.push((&self.text[offset..], false));
+        }
+        fragments
+    }
+
+    pub fn highlights(&self) -> &[Range<usize>] {
+        self.highlights.as_slice()
+    }
+
+    pub fn line_number(&self) -> LineNumber {
+        self.line_number
+    }
+
+    pub fn text(&self) -> &str {
+        self.text.as_str()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let l1 = LineNumber::try_from(1_usize)?;
+        let snippet = Snippet::new(l1, "foo bar foo", &[8..11, 0..3]);
+        assert_eq!(snippet.line_number(), l1);
+        assert_eq!(snippet.text(), "foo bar foo");
+        assert_eq!(snippet.highlights(), &[0..3, 8..11]);
+        assert_eq!(
+            snippet.fragments(),
+            vec![("foo", true), (" bar ", false), ("foo", true)]
+        );
+
+        let snippet = Snippet::new(l1, "abc", &[0..2, 1..3]);
+        assert_eq!(snippet.fragments(), vec![("abc", true)]);
+
+        let snippet = Snippet::new(l1, "abc", &[]);
+        assert_eq!(snippet.fragments(), vec![("abc", false)]);
+        Ok(())
+    }
+
+    #[test]
+    fn truncate_test() -> anyhow::Result<()> {
+        let l1 = LineNumber::try_from(1_usize)?;
+        let line = format!("{}foo{}", "あ".repeat(50), "い".repeat(100));
+        // the second match is truncated
+        let snippet = Snippet::new(l1, line.as_str(), &[150..153, 390..393]);
+        assert_eq!(
+            snippet.text(),
+            format!("…{}foo{}…", "あ".repeat(40), "い".repeat(77))
+        );
+        assert_eq!(snippet.fragments().len(), 3);
+        assert_eq!(snippet.fragments()[1], ("foo", true));
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct QueryOptions {
+    pub ignore_case: bool,
+    pub regex: bool,
+    pub word: bool,
+}
+
+#[derive(Clone, Debug)]
+pub enum TextMatcher {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl TextMatcher {
+    pub fn new(pattern: &str, options: &QueryOptions) -> Result<Self, regex::Error> {
+        if !options.ignore_case && !options.regex && !options.word {
+            return Ok(Self::Literal(pattern.to_string()));
+        }
+        let pattern = if options.regex {
+            pattern.to_string()
+        } else {
+            regex::escape(pattern)
+        };
+        let pattern = if options.word {
+            format!(r"\b(?:{})\b", pattern)
+        } else {
+            pattern
+        };
+        RegexBuilder::new(pattern.as_str())
+            .case_insensitive(options.ignore_case)
+            .build()
+            .map(Self::Regex)
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            TextMatcher::Literal(s) => s.as_str(),
+            TextMatcher::Regex(regex) => regex.as_str(),
+        }
+    }
+
+    // returns the non-empty byte ranges of the matches
+    pub fn find_iter(&self, s: &str) -> Vec<Range<usize>> {
+        match self {
+            TextMatcher::Literal(pattern) if pattern.is_empty() => vec![],
+            TextMatcher::Literal(pattern) => s
+                .match_indices(pattern.as_str())
+                .map(|(start, m)| start..start + m.len())
+                .collect(),
+            TextMatcher::Regex(regex) => regex
+                .find_iter(s)
+                .map(|m| m.range())
+                .filter(|range| !range.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn is_match(&self, s: &str) -> bool {
+        match self {
+            TextMatcher::Literal(pattern) => s.contains(pattern.as_str()),
+            TextMatcher::Regex(regex) => regex.is_match(s),
+        }
+    }
+}
+
+impl PartialEq for TextMatcher {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (TextMatcher::Literal(a), TextMatcher::Literal(b)) => a == b,
+            (TextMatcher::Regex(a), TextMatcher::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for TextMatcher {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn literal_test() -> anyhow::Result<()> {
+        let matcher = TextMatcher::new("a.", &QueryOptions::default())?;
+        assert_eq!(matcher, TextMatcher::Literal("a.".to_string()));
+        assert!(matcher.is_match("xa."));
+        assert!(!matcher.is_match("ab"));
+        assert_eq!(matcher.find_iter("a.a.ab"), vec![0..2, 2..4]);
+        assert!(TextMatcher::new("", &QueryOptions::default())?
+            .find_iter("abc")
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn ignore_case_test() -> anyhow::Result<()> {
+        let options = QueryOptions {
+            ignore_case: true,
+            ..Default::default()
+        };
+        let matcher = TextMatcher::new("a.", &options)?;
+        assert!(matcher.is_match("A."));
+        assert!(!matcher.is_match("Ab"));
+        assert_eq!(matcher.find_iter("a.A.ab"), vec![0..2, 2..4]);
+        Ok(())
+    }
+
+    #[test]
+    fn regex_test() -> anyhow::Result<()> {
+        let options = QueryOptions {
+            regex: true,
+            ..Default::default()
+        };
+        let matcher = TextMatcher::new("a.", &options)?;
+        assert!(matcher.is_match("ab"));
+        assert!(!matcher.is_match("A."));
+        assert_eq!(matcher.find_iter("a.Abab"), vec![0..2, 4..6]);
+        assert!(TextMatcher::new("x*", &options)?
+            .find_iter("abc")
+            .is_empty());
+        assert!(TextMatcher::new("(", &options).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn word_test() -> anyhow::Result<()> {
+        let options = QueryOptions {
+            word: true,
+            ..Default::default()
+        };
+        let matcher = TextMatcher::new("foo", &options)?;
+        assert!(matcher.is_match("a foo b"));
+        assert!(!matcher.is_match("foobar"));
+        assert_eq!(matcher.find_iter("foo foobar (foo)"), vec![0..3, 12..15]);
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    convert::TryFrom,
+    ops::Range,
+};
+
+use crate::{ColumnNumber, LineNumber};
+
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct TextSpan {
+    // byte offsets in the text
+    start: usize,
+    end: usize,
+    // (line, char column) of the start and the end (end exclusive)
+    start_position: (LineNumber, ColumnNumber),
+    end_position: (LineNumber, ColumnNumber),
+}
+
+// the positions of the offsets in one pass over the text
+fn positions(text: &str, offsets: BTreeSet<usize>) -> BTreeMap<usize, (LineNumber, ColumnNumber)> {
+    let mut res = BTreeMap::new();
+    let (mut line, mut column) = (1_usize, 1_usize);
+    let mut chars = text.char_indices().peekable();
+    for offset in offsets {
+        while let Some((_, c)) = chars.next_if(|(index, _)| *index < offset) {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        res.insert(
+            offset,
+            (
+                LineNumber::try_from(line).expect("invalid line"),
+                ColumnNumber::try_from(column).expect("invalid column"),
+            ),
+        );
+    }
+    res
+}
+
+impl TextSpan {
+    pub fn new(text: &str, byte_range: Range<usize>) -> Self {
+        Self::new_all(text, vec![byte_range])
+            .pop()
+            .expect("a span for the range")
+    }
+
+    // creates the spans in the text at once (the text is scanned only once)
+    pub fn new_all(text: &str, byte_ranges: Vec<Range<usize>>) -> Vec<Self> {
+        let positions = positions(
+            text,
+            byte_ranges
+                .iter()
+                .flat_map(|byte_range| [byte_range.start, byte_range.end])
+                .collect::<BTreeSet<usize>>(),
+        );
+        byte_ranges
+            .into_iter()
+            .map(|byte_range| Self {
+                start: byte_range.start,
+                end: byte_range.end,
+                start_position: positions[&byte_range.start],
+                end_position: positions[&byte_range.end],
+            })
+            .collect()
+    }
+
+    pub fn byte_range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    pub fn column_number(&self) -> ColumnNumber {
+        self.start_position.1
+    }
+
+    pub fn end_column_number(&self) -> ColumnNumber {
+        self.end_position.1
+    }
+
+    pub fn end_line_number(&self) -> LineNumber {
+        self.end_position.0
+    }
+
+    pub fn line_number(&self) -> LineNumber {
+        self.start_position.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let text = "abc\nあいう [link](url)\n";
+        let span = TextSpan::new(text, 14..25);
+        assert_eq!(&text[span.byte_range()], "[link](url)");
+        assert_eq!(span.line_number(), LineNumber::try_from(2_usize)?);
+        assert_eq!(span.column_number(), ColumnNumber::try_from(5_usize)?);
+        assert_eq!(span.end_line_number(), LineNumber::try_from(2_usize)?);
+        assert_eq!(span.end_column_number(), ColumnNumber::try_from(16_usize)?);
+
+        let span = TextSpan::new(text, 0..7);
+        assert_eq!(span.line_number(), LineNumber::try_from(1_usize)?);
+        assert_eq!(span.column_number(), ColumnNumber::try_from(1_usize)?);
+        assert_eq!(span.end_line_number(), LineNumber::try_from(2_usize)?);
+        assert_eq!(span.end_column_number(), ColumnNumber::try_from(2_usize)?);
+
+        let spans = TextSpan::new_all(text, vec![14..25, 0..7, 4..4, 25..26]);
+        assert_eq!(spans[0], TextSpan::new(text, 14..25));
+        assert_eq!(spans[1], TextSpan::new(text, 0..7));
+        assert_eq!(spans[2].line_number(), LineNumber::try_from(2_usize)?);
+        assert_eq!(spans[2].column_number(), ColumnNumber::try_from(1_usize)?);
+        assert_eq!(spans[3].end_line_number(), LineNumber::try_from(3_usize)?);
+        assert_eq!(
+            spans[3].end_column_number(),
+            ColumnNumber::try_from(1_usize)?
+        );
+        Ok(())
+    }
+}
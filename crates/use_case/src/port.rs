@@ -1,3 +1,11 @@
+mod lock_poisoned_error;
+mod page_content_template_repository;
+mod page_obsoleted_error;
 mod page_repository;
+mod page_titled_error;
 
+pub use self::lock_poisoned_error::*;
+pub use self::page_content_template_repository::*;
+pub use self::page_obsoleted_error::*;
 pub use self::page_repository::*;
+pub use self::page_titled_error::*;
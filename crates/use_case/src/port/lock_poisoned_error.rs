@@ -0,0 +1,6 @@
+use thiserror::Error;
+
+// a thread panicked while holding the lock of a repository cache
+#[derive(Debug, Error)]
+#[error("failed to lock {0}")]
+pub struct LockPoisonedError(pub &'static str);
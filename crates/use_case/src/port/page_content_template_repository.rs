@@ -0,0 +1,17 @@
+use entity::PageContentTemplate;
+#[cfg(test)]
+use mockall::automock;
+
+#[cfg_attr(test, automock)]
+pub trait PageContentTemplateRepository {
+    fn find_by_name(&self, name: &str) -> anyhow::Result<Option<PageContentTemplate>>;
+
+    // sorted by name
+    fn find_names(&self) -> anyhow::Result<Vec<String>>;
+}
+
+pub trait HasPageContentTemplateRepository {
+    type PageContentTemplateRepository: PageContentTemplateRepository;
+
+    fn page_content_template_repository(&self) -> &Self::PageContentTemplateRepository;
+}
@@ -0,0 +1,7 @@
+use entity::PageId;
+use thiserror::Error;
+
+// another page obsoletes the page already (a new version would fork the history)
+#[derive(Debug, Error)]
+#[error("page has been obsoleted: {0}")]
+pub struct PageObsoletedError(pub PageId);
@@ -1,34 +1,55 @@
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, convert::TryFrom, sync::Arc};
 
-use entity::{ColumnNumber, LineNumber, Page, PageGraph, PageId, PageTitle, Query};
+use entity::{LineNumber, Page, PageContent, PageGraph, PageId, PageTitle, Query, QueryMatch};
 #[cfg(test)]
 use mockall::automock;
 
 #[cfg_attr(test, automock)]
 pub trait PageRepository {
+    // saves the page only if the page id is not used (returns false if used)
+    fn create(&self, page: Page) -> anyhow::Result<bool>;
+
+    // `create` for a page that obsoletes `obsoleted`
+    // fails with `PageObsoletedError` if another page obsoletes it already
+    // the check and the write are atomic, so concurrent edits don't fork the history
+    fn create_if_not_obsoleted(&self, page: Page, obsoleted: &PageId) -> anyhow::Result<bool>;
+
+    // `create` for a page whose title no non-obsoleted page has
+    // fails with `PageTitledError` if another page has the title already
+    // the check and the write are atomic, so concurrent requests don't duplicate the page
+    fn create_if_untitled(&self, page: Page) -> anyhow::Result<bool>;
+
     fn destroy(&self, page_id: &PageId) -> anyhow::Result<bool>;
 
     fn destroy_cache(&self, page_id: &PageId) -> anyhow::Result<bool>;
 
     fn find_by_id(&self, page_id: &PageId) -> anyhow::Result<Option<Page>>;
 
-    fn find_by_obsoleted(&self, page_id: &PageId) -> BTreeSet<PageId>;
+    fn find_by_obsoleted(&self, page_id: &PageId) -> anyhow::Result<BTreeSet<PageId>>;
 
-    // TODO: add tests
-    fn find_by_query(
-        &self,
-        query: &Query,
-    ) -> anyhow::Result<Vec<(PageId, LineNumber, ColumnNumber)>> {
+    fn find_by_query(&self, query: &Query) -> anyhow::Result<Vec<(PageId, QueryMatch)>> {
+        let page_graph = if query.expr().uses_page_graph() {
+            self.load_page_graph()?
+        } else {
+            Arc::new(PageGraph::default())
+        };
         let mut res = vec![];
-        for page_id in self.find_ids()? {
+        for page_id in self.find_candidate_ids(query)? {
             let page = self.find_by_id(&page_id)?;
             if let Some(page) = page {
-                res.extend(
-                    query
-                        .matches(String::from(page.content().clone()).as_str()) // TODO: clone
-                        .into_iter()
-                        .map(|(l, c)| (page_id, l, c)),
-                );
+                if !query.is_match(&page, &page_graph) {
+                    continue;
+                }
+                let matches = query.matches(page.content().as_str());
+                if matches.is_empty() {
+                    // matched by fields only (e.g. "title:foo")
+                    res.push((
+                        page_id,
+                        QueryMatch::new(LineNumber::try_from(1_usize)?, "", 0..0),
+                    ));
+                } else {
+                    res.extend(matches.into_iter().map(|m| (page_id, m)));
+                }
             }
         }
         Ok(res)
@@ -36,21 +57,54 @@ pub trait PageRepository {
 
     fn find_by_title(&self, page_title: &PageTitle) -> anyhow::Result<Vec<Option<Page>>>;
 
+    // returns the ids of the pages that may match the query (e.g. using a search index)
+    fn find_candidate_ids(&self, _query: &Query) -> anyhow::Result<Vec<PageId>> {
+        self.find_ids()
+    }
+
     fn find_ids(&self) -> anyhow::Result<Vec<PageId>>;
 
-    fn load_page_graph(&self) -> anyhow::Result<PageGraph> {
+    fn find_trashed_by_id(&self, page_id: &PageId) -> anyhow::Result<Option<Page>>;
+
+    fn find_trashed_ids(&self) -> anyhow::Result<Vec<PageId>>;
+
+    // returns false if the cached page graph (if any) differs from the stored pages
+    fn is_cache_consistent(&self) -> anyhow::Result<bool> {
+        Ok(true)
+    }
+
+    // returns the page graph shared with the cache (if any), so it is cheap to call
+    fn load_page_graph(&self) -> anyhow::Result<Arc<PageGraph>> {
         let mut page_graph = PageGraph::default();
         for page_id in self.find_ids()? {
             if let Some(page) = self.find_by_id(&page_id)? {
                 page_graph.add_page(page);
             }
         }
-        Ok(page_graph)
+        Ok(Arc::new(page_graph))
     }
 
+    // removes the page from the trash permanently
+    fn purge(&self, page_id: &PageId) -> anyhow::Result<bool>;
+
+    // rebuilds the search index (if any)
+    fn reindex(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    // moves the page back from the trash (fails if the page id is used)
+    fn restore(&self, page_id: &PageId) -> anyhow::Result<bool>;
+
     fn save(&self, page: Page) -> anyhow::Result<()>;
 
+    // saves the page only if the stored content is `expected` (returns false if not)
+    // the check and the write are atomic, so concurrent updates don't overwrite each other
+    fn save_if(&self, page: Page, expected: &PageContent) -> anyhow::Result<bool>;
+
     fn save_cache(&self, page: Page) -> anyhow::Result<()>;
+
+    // moves the page to the trash
+    fn trash(&self, page_id: &PageId) -> anyhow::Result<bool>;
 }
 
 pub trait HasPageRepository {
@@ -63,67 +117,102 @@ pub trait HasPageRepository {
 mod tests {
     use std::str::FromStr;
 
-    use entity::PageContent;
-
     use super::*;
 
-    #[test]
-    fn load_page_graph_test() -> anyhow::Result<()> {
-        struct TestRepository {}
-        impl PageRepository for TestRepository {
-            fn destroy_cache(&self, _: &PageId) -> anyhow::Result<bool> {
-                unreachable!()
-            }
+    struct TestRepository {}
+    impl PageRepository for TestRepository {
+        fn create(&self, _: Page) -> anyhow::Result<bool> {
+            unreachable!()
+        }
 
-            fn destroy(&self, _: &PageId) -> anyhow::Result<bool> {
-                unreachable!()
-            }
+        fn create_if_not_obsoleted(&self, _: Page, _: &PageId) -> anyhow::Result<bool> {
+            unreachable!()
+        }
 
-            fn find_by_id(&self, page_id: &PageId) -> anyhow::Result<Option<Page>> {
-                let page_id1 = PageId::from_str("20210203T040506Z")?;
-                let page_id2 = PageId::from_str("20210203T040507Z")?;
-                let page_content1 = PageContent::from("# title1".to_string());
-                let page_content2 = PageContent::from(
-                    vec![
-                        "# title1",
-                        "",
-                        "## Obsoletes",
-                        "",
-                        "- [20210203T040506Z](/pages/20210203T040506Z)",
-                    ]
-                    .join("\n"),
-                );
-                if page_id == &page_id1 {
-                    Ok(Some(Page::new(page_id1, page_content1)))
-                } else if page_id == &page_id2 {
-                    Ok(Some(Page::new(page_id2, page_content2)))
-                } else {
-                    unreachable!()
-                }
-            }
+        fn create_if_untitled(&self, _: Page) -> anyhow::Result<bool> {
+            unreachable!()
+        }
 
-            fn find_by_obsoleted(&self, _: &PageId) -> BTreeSet<PageId> {
-                unreachable!()
-            }
+        fn destroy_cache(&self, _: &PageId) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+
+        fn destroy(&self, _: &PageId) -> anyhow::Result<bool> {
+            unreachable!()
+        }
 
-            fn find_by_title(&self, _: &PageTitle) -> anyhow::Result<Vec<Option<Page>>> {
+        fn find_by_id(&self, page_id: &PageId) -> anyhow::Result<Option<Page>> {
+            let page_id1 = PageId::from_str("20210203T040506Z")?;
+            let page_id2 = PageId::from_str("20210203T040507Z")?;
+            let page_content1 = PageContent::from("# title1".to_string());
+            let page_content2 = PageContent::from(
+                vec![
+                    "# title1",
+                    "",
+                    "## Obsoletes",
+                    "",
+                    "- [20210203T040506Z](/pages/20210203T040506Z)",
+                ]
+                .join("\n"),
+            );
+            if page_id == &page_id1 {
+                Ok(Some(Page::new(page_id1, page_content1)))
+            } else if page_id == &page_id2 {
+                Ok(Some(Page::new(page_id2, page_content2)))
+            } else {
                 unreachable!()
             }
+        }
 
-            fn find_ids(&self) -> anyhow::Result<Vec<PageId>> {
-                let page_id1 = PageId::from_str("20210203T040506Z")?;
-                let page_id2 = PageId::from_str("20210203T040507Z")?;
-                Ok(vec![page_id1, page_id2])
-            }
+        fn find_by_obsoleted(&self, _: &PageId) -> anyhow::Result<BTreeSet<PageId>> {
+            unreachable!()
+        }
 
-            fn save_cache(&self, _: Page) -> anyhow::Result<()> {
-                unreachable!()
-            }
+        fn find_by_title(&self, _: &PageTitle) -> anyhow::Result<Vec<Option<Page>>> {
+            unreachable!()
+        }
 
-            fn save(&self, _: Page) -> anyhow::Result<()> {
-                unreachable!()
-            }
+        fn find_ids(&self) -> anyhow::Result<Vec<PageId>> {
+            let page_id1 = PageId::from_str("20210203T040506Z")?;
+            let page_id2 = PageId::from_str("20210203T040507Z")?;
+            Ok(vec![page_id1, page_id2])
+        }
+
+        fn find_trashed_by_id(&self, _: &PageId) -> anyhow::Result<Option<Page>> {
+            unreachable!()
+        }
+
+        fn find_trashed_ids(&self) -> anyhow::Result<Vec<PageId>> {
+            unreachable!()
+        }
+
+        fn purge(&self, _: &PageId) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+
+        fn restore(&self, _: &PageId) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+
+        fn save_cache(&self, _: Page) -> anyhow::Result<()> {
+            unreachable!()
+        }
+
+        fn save(&self, _: Page) -> anyhow::Result<()> {
+            unreachable!()
+        }
+
+        fn save_if(&self, _: Page, _: &PageContent) -> anyhow::Result<bool> {
+            unreachable!()
+        }
+
+        fn trash(&self, _: &PageId) -> anyhow::Result<bool> {
+            unreachable!()
         }
+    }
+
+    #[test]
+    fn load_page_graph_test() -> anyhow::Result<()> {
         let page_repository = TestRepository {};
         let mut expected = PageGraph::default();
         let page_id1 = PageId::from_str("20210203T040506Z")?;
@@ -142,7 +231,42 @@ mod tests {
         );
         expected.add_page(Page::new(page_id1, page_content1));
         expected.add_page(Page::new(page_id2, page_content2));
-        assert_eq!(page_repository.load_page_graph()?, expected);
+        assert_eq!(*page_repository.load_page_graph()?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn find_by_query_test() -> anyhow::Result<()> {
+        let page_repository = TestRepository {};
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let positions = |query: &str| -> anyhow::Result<Vec<(PageId, usize, usize)>> {
+            Ok(page_repository
+                .find_by_query(&Query::from_str(query)?)?
+                .into_iter()
+                .map(|(page_id, m)| {
+                    (
+                        page_id,
+                        usize::from(m.line_number()),
+                        usize::from(m.column_number()),
+                    )
+                })
+                .collect())
+        };
+        assert_eq!(
+            positions("title1")?,
+            vec![(page_id1, 1, 3), (page_id2, 1, 3)]
+        );
+        assert_eq!(positions("Obsoletes")?, vec![(page_id2, 3, 4)]);
+        assert_eq!(positions("obsoleted:false")?, vec![(page_id2, 1, 1)]);
+        assert_eq!(
+            positions("title:title1 -Obsoletes")?,
+            vec![(page_id1, 1, 1)]
+        );
+        assert_eq!(
+            positions("20210203T040506Z")?,
+            vec![(page_id2, 5, 4), (page_id2, 5, 29)]
+        );
         Ok(())
     }
 }
@@ -0,0 +1,7 @@
+use entity::PageId;
+use thiserror::Error;
+
+// another page has the title already (a new page would duplicate it)
+#[derive(Debug, Error)]
+#[error("page with the title exists: {0}")]
+pub struct PageTitledError(pub PageId);
@@ -1,11 +1,29 @@
+mod check_links_use_case;
+mod convert_links_use_case;
+mod create_page_use_case;
+mod daily_page_use_case;
+mod delete_page_use_case;
 mod edit_page_use_case;
 mod ensure_links_use_case;
 mod list_pages_use_case;
 mod list_titles_use_case;
+mod new_page_from_template_use_case;
 mod new_page_use_case;
+mod rename_title_use_case;
+mod search_pages_use_case;
+mod unlinked_mentions_use_case;
 
+pub use self::check_links_use_case::*;
+pub use self::convert_links_use_case::*;
+pub use self::create_page_use_case::*;
+pub use self::daily_page_use_case::*;
+pub use self::delete_page_use_case::*;
 pub use self::edit_page_use_case::*;
 pub use self::ensure_links_use_case::*;
 pub use self::list_pages_use_case::*;
 pub use self::list_titles_use_case::*;
+pub use self::new_page_from_template_use_case::*;
 pub use self::new_page_use_case::*;
+pub use self::rename_title_use_case::*;
+pub use self::search_pages_use_case::*;
+pub use self::unlinked_mentions_use_case::*;
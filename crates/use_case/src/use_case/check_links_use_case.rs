@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+
+use entity::{PageId, PageTitle};
+
+use crate::{HasPageRepository, PageRepository};
+
+pub trait CheckLinksUseCase: HasPageRepository {
+    // the pages that link to obsoleted versions
+    // `all` includes the obsoleted pages
+    fn find_obsoleted_page_links(
+        &self,
+        all: bool,
+    ) -> anyhow::Result<Vec<(PageId, BTreeSet<PageId>)>> {
+        let page_graph = self.page_repository().load_page_graph()?;
+        Ok(self
+            .page_repository()
+            .find_ids()?
+            .into_iter()
+            .filter(|page_id| all || !page_graph.is_obsoleted(page_id))
+            .map(|page_id| (page_id, page_graph.obsoleted_page_links(&page_id)))
+            .filter(|(_, page_ids)| !page_ids.is_empty())
+            .collect())
+    }
+
+    // the titles that no page has with the pages that link to them (most linked first)
+    // `all` includes the links in obsoleted pages
+    fn find_wanted_titles(&self, all: bool) -> anyhow::Result<Vec<(PageTitle, BTreeSet<PageId>)>> {
+        let page_graph = self.page_repository().load_page_graph()?;
+        let mut wanted_titles = page_graph
+            .wanted_titles()
+            .into_iter()
+            .map(|(page_title, page_ids)| {
+                let page_ids = page_ids
+                    .into_iter()
+                    .filter(|page_id| all || !page_graph.is_obsoleted(page_id))
+                    .collect::<BTreeSet<PageId>>();
+                (page_title, page_ids)
+            })
+            .filter(|(_, page_ids)| !page_ids.is_empty())
+            .collect::<Vec<(PageTitle, BTreeSet<PageId>)>>();
+        wanted_titles
+            .sort_by(|(t1, ids1), (t2, ids2)| ids2.len().cmp(&ids1.len()).then(t1.cmp(t2)));
+        Ok(wanted_titles)
+    }
+}
+
+impl<T: HasPageRepository> CheckLinksUseCase for T {}
+
+pub trait HasCheckLinksUseCase {
+    type CheckLinksUseCase: CheckLinksUseCase;
+
+    fn check_links_use_case(&self) -> &Self::CheckLinksUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use entity::{Page, PageContent, PageGraph};
+
+    use super::*;
+    use crate::MockPageRepository;
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasCheckLinksUseCase for TestApp {
+        type CheckLinksUseCase = TestApp;
+
+        fn check_links_use_case(&self) -> &Self::CheckLinksUseCase {
+            self
+        }
+    }
+
+    fn pages() -> anyhow::Result<Vec<Page>> {
+        Ok(vec![
+            Page::new(
+                PageId::from_str("20210203T040506Z")?,
+                PageContent::from("# foo\n\n[[bar]] [[baz]]".to_string()),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040507Z")?,
+                PageContent::from(
+                    vec![
+                        "# foo",
+                        "",
+                        "[[baz]] [[qux]]",
+                        "",
+                        "## Obsoletes",
+                        "",
+                        "- [20210203T040506Z](/pages/20210203T040506Z)",
+                        "",
+                    ]
+                    .join("\n"),
+                ),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040508Z")?,
+                PageContent::from(
+                    "# quux\n\n[[baz]] [old](/pages/20210203T040506Z) [[foo]]".to_string(),
+                ),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040509Z")?,
+                PageContent::from(
+                    vec![
+                        "# quux",
+                        "",
+                        "[[baz]] [old](/pages/20210203T040506Z) [[foo]]",
+                        "",
+                        "## Obsoletes",
+                        "",
+                        "- [20210203T040508Z](/pages/20210203T040508Z)",
+                        "",
+                    ]
+                    .join("\n"),
+                ),
+            ),
+        ])
+    }
+
+    fn app() -> TestApp {
+        let mut page_repository = MockPageRepository::new();
+        page_repository.expect_load_page_graph().returning(|| {
+            let mut page_graph = PageGraph::default();
+            for page in pages()? {
+                page_graph.add_page(page);
+            }
+            Ok(Arc::new(page_graph))
+        });
+        page_repository
+            .expect_find_ids()
+            .returning(|| Ok(pages()?.iter().map(|page| *page.id()).collect()));
+        TestApp { page_repository }
+    }
+
+    #[test]
+    fn find_obsoleted_page_links_test() -> anyhow::Result<()> {
+        let app = app();
+        let old = vec![PageId::from_str("20210203T040506Z")?]
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        // `20210203T040508Z` is obsoleted
+        assert_eq!(
+            app.check_links_use_case()
+                .find_obsoleted_page_links(false)?,
+            vec![(PageId::from_str("20210203T040509Z")?, old.clone())]
+        );
+        assert_eq!(
+            app.check_links_use_case().find_obsoleted_page_links(true)?,
+            vec![
+                (PageId::from_str("20210203T040508Z")?, old.clone()),
+                (PageId::from_str("20210203T040509Z")?, old)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn find_wanted_titles_test() -> anyhow::Result<()> {
+        let app = app();
+        let to_strings = |wanted_titles: Vec<(PageTitle, BTreeSet<PageId>)>| {
+            wanted_titles
+                .into_iter()
+                .map(|(page_title, page_ids)| (page_title.to_string(), page_ids.len()))
+                .collect::<Vec<(String, usize)>>()
+        };
+        // `bar` is linked only by the obsoleted page
+        assert_eq!(
+            to_strings(app.check_links_use_case().find_wanted_titles(false)?),
+            vec![("baz".to_string(), 2), ("qux".to_string(), 1)]
+        );
+        assert_eq!(
+            to_strings(app.check_links_use_case().find_wanted_titles(true)?),
+            vec![
+                ("baz".to_string(), 4),
+                ("bar".to_string(), 1),
+                ("qux".to_string(), 1)
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+use anyhow::{anyhow, Context};
+use entity::{Page, PageId, PageLinkStyle};
+
+use crate::{HasPageRepository, PageRepository};
+
+pub trait ConvertLinksUseCase: HasPageRepository {
+    // rewrites the title links in place (like `ensure_links`)
+    // returns the ids of the changed pages
+    fn convert_links(
+        &self,
+        page_id: Option<&PageId>,
+        page_link_style: PageLinkStyle,
+    ) -> anyhow::Result<Vec<PageId>> {
+        let page_ids = match page_id {
+            Some(page_id) => vec![*page_id],
+            None => self.page_repository().find_ids()?,
+        };
+        let mut res = vec![];
+        for page_id in page_ids {
+            let page = self
+                .page_repository()
+                .find_by_id(&page_id)?
+                .with_context(|| anyhow!("file not found: {}", page_id))?;
+            let mut page_content = page.content().clone();
+            page_content.convert_links(page_link_style);
+            if &page_content == page.content() {
+                continue;
+            }
+            self.page_repository()
+                .save(Page::new(page_id, page_content))?;
+            res.push(page_id);
+        }
+        Ok(res)
+    }
+}
+
+impl<T: HasPageRepository> ConvertLinksUseCase for T {}
+
+pub trait HasConvertLinksUseCase {
+    type ConvertLinksUseCase: ConvertLinksUseCase;
+
+    fn convert_links_use_case(&self) -> &Self::ConvertLinksUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use entity::PageContent;
+    use mockall::predicate;
+
+    use super::*;
+    use crate::MockPageRepository;
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasConvertLinksUseCase for TestApp {
+        type ConvertLinksUseCase = TestApp;
+
+        fn convert_links_use_case(&self) -> &Self::ConvertLinksUseCase {
+            self
+        }
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let mut page_repository = MockPageRepository::new();
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        page_repository
+            .expect_find_ids()
+            .returning(move || Ok(vec![page_id1, page_id2]));
+        page_repository
+            .expect_find_by_id()
+            .with(predicate::eq(page_id1))
+            .returning(move |_| {
+                Ok(Some(Page::new(
+                    page_id1,
+                    PageContent::from(vec!["# title", "", "[[link1]]", ""].join("\n")),
+                )))
+            });
+        page_repository
+            .expect_find_by_id()
+            .with(predicate::eq(page_id2))
+            .returning(move |_| {
+                Ok(Some(Page::new(
+                    page_id2,
+                    PageContent::from(vec!["# title", "", "no links", ""].join("\n")),
+                )))
+            });
+        // the unchanged page is not saved
+        page_repository
+            .expect_save()
+            .with(predicate::eq(Page::new(
+                page_id1,
+                PageContent::from(vec!["# title", "", "[link1](/titles/link1)", ""].join("\n")),
+            )))
+            .times(1)
+            .returning(|_| Ok(()));
+        let app = TestApp { page_repository };
+        let page_ids = app
+            .convert_links_use_case()
+            .convert_links(None, PageLinkStyle::Markdown)?;
+        assert_eq!(page_ids, vec![page_id1]);
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use anyhow::{anyhow, Context};
+use entity::{Page, PageContent, PageId};
+
+use crate::{HasPageRepository, PageRepository};
+
+// gives up after this many used ids (seconds)
+const MAX_ATTEMPTS: usize = 60;
+
+// tries the ids from now until `create` succeeds (returns false if the id is used)
+// starts after `after` if it is not older (e.g. the last id allocated in the same run)
+fn create_after(
+    after: Option<&PageId>,
+    mut create: impl FnMut(&PageId) -> anyhow::Result<bool>,
+) -> anyhow::Result<PageId> {
+    let now = PageId::new().context("This application is out of date.")?;
+    let mut page_id = match after {
+        Some(after) if after >= &now => after.next().context("This application is out of date.")?,
+        _ => now,
+    };
+    for _ in 0..MAX_ATTEMPTS {
+        if create(&page_id)? {
+            return Ok(page_id);
+        }
+        page_id = page_id.next().context("This application is out of date.")?;
+    }
+    Err(anyhow!("no free page id found: {}", page_id))
+}
+
+pub trait CreatePageUseCase: HasPageRepository {
+    // never overwrites an existing page, even if created within the same second
+    fn create_page(&self, page_content: PageContent) -> anyhow::Result<PageId> {
+        self.create_page_with(|_| page_content.clone())
+    }
+
+    // creates a page that obsoletes the page (the content has `## Obsoletes`)
+    // fails with `PageObsoletedError` if another page obsoletes it already
+    // `after` is the last id allocated in the same run (the ids up to it are skipped)
+    fn create_page_obsoleting(
+        &self,
+        page_content: PageContent,
+        obsoleted: &PageId,
+        after: Option<&PageId>,
+    ) -> anyhow::Result<PageId> {
+        create_after(after, |page_id| {
+            self.page_repository()
+                .create_if_not_obsoleted(Page::new(*page_id, page_content.clone()), obsoleted)
+        })
+    }
+
+    // creates a page unless another page has the title
+    // fails with `PageTitledError` if another page has the title already
+    fn create_page_if_untitled(&self, page_content: PageContent) -> anyhow::Result<PageId> {
+        create_after(None, |page_id| {
+            self.page_repository()
+                .create_if_untitled(Page::new(*page_id, page_content.clone()))
+        })
+    }
+
+    // builds the content for the allocated page id (e.g. from a template)
+    fn create_page_with(
+        &self,
+        page_content: impl Fn(&PageId) -> PageContent,
+    ) -> anyhow::Result<PageId> {
+        create_after(None, |page_id| {
+            self.page_repository()
+                .create(Page::new(*page_id, page_content(page_id)))
+        })
+    }
+}
+
+impl<T: HasPageRepository> CreatePageUseCase for T {}
+
+pub trait HasCreatePageUseCase {
+    type CreatePageUseCase: CreatePageUseCase;
+
+    fn create_page_use_case(&self) -> &Self::CreatePageUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::MockPageRepository;
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasCreatePageUseCase for TestApp {
+        type CreatePageUseCase = TestApp;
+
+        fn create_page_use_case(&self) -> &Self::CreatePageUseCase {
+            self
+        }
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let mut page_repository = MockPageRepository::new();
+        let used = Arc::new(Mutex::new(vec![]));
+        let used_in_mock = used.clone();
+        // the first two ids are used
+        page_repository.expect_create().returning(move |page| {
+            let mut used = used_in_mock.lock().unwrap();
+            used.push(*page.id());
+            Ok(used.len() > 2)
+        });
+        let app = TestApp { page_repository };
+        let page_id = app
+            .create_page_use_case()
+            .create_page(PageContent::from("# title1".to_string()))?;
+        let used = used.lock().unwrap().clone();
+        assert_eq!(used.len(), 3);
+        assert_eq!(used[0].next(), Some(used[1]));
+        assert_eq!(used[1].next(), Some(used[2]));
+        assert_eq!(page_id, used[2]);
+        Ok(())
+    }
+}
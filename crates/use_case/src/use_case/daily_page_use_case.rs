@@ -0,0 +1,126 @@
+use chrono::NaiveDate;
+use entity::{DailyTitleFormat, PageId};
+
+use crate::{CreatePageUseCase, HasPageRepository, PageRepository, PageTitledError};
+
+pub trait DailyPageUseCase: CreatePageUseCase {
+    // finds or creates the non-obsoleted daily page (returns true if created)
+    fn daily_page(
+        &self,
+        date: NaiveDate,
+        format: &DailyTitleFormat,
+    ) -> anyhow::Result<(PageId, bool)> {
+        let page_graph = self.page_repository().load_page_graph()?;
+        let found = page_graph
+            .titled(&format.title(date))
+            .into_iter()
+            .filter(|page_id| !page_graph.is_obsoleted(page_id))
+            .max();
+        if let Some(page_id) = found {
+            return Ok((page_id, false));
+        }
+        // another request may create the page after the lookup
+        match self.create_page_if_untitled(format.content(date)) {
+            Ok(page_id) => Ok((page_id, true)),
+            Err(e) => match e.downcast_ref::<PageTitledError>() {
+                Some(PageTitledError(page_id)) => Ok((*page_id, false)),
+                None => Err(e),
+            },
+        }
+    }
+}
+
+impl<T: HasPageRepository> DailyPageUseCase for T {}
+
+pub trait HasDailyPageUseCase {
+    type DailyPageUseCase: DailyPageUseCase;
+
+    fn daily_page_use_case(&self) -> &Self::DailyPageUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use entity::{Page, PageContent, PageGraph};
+
+    use super::*;
+    use crate::MockPageRepository;
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasDailyPageUseCase for TestApp {
+        type DailyPageUseCase = TestApp;
+
+        fn daily_page_use_case(&self) -> &Self::DailyPageUseCase {
+            self
+        }
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let mut page_repository = MockPageRepository::new();
+        page_repository.expect_load_page_graph().returning(|| {
+            let mut page_graph = PageGraph::default();
+            page_graph.add_page(Page::new(
+                PageId::from_str("20210203T040506Z")?,
+                PageContent::from("# 2021-02-03".to_string()),
+            ));
+            page_graph.add_page(Page::new(
+                PageId::from_str("20210203T040507Z")?,
+                PageContent::from(
+                    vec![
+                        "# 2021-02-03",
+                        "",
+                        "## Obsoletes",
+                        "",
+                        "- [20210203T040506Z](/pages/20210203T040506Z)",
+                    ]
+                    .join("\n"),
+                ),
+            ));
+            Ok(Arc::new(page_graph))
+        });
+        page_repository
+            .expect_create_if_untitled()
+            .withf(|page| page.title().as_str() == "2021-02-04")
+            .times(1)
+            .returning(|_| Ok(true));
+        // created by another request after the lookup
+        page_repository
+            .expect_create_if_untitled()
+            .withf(|page| page.title().as_str() == "2021-02-05")
+            .times(1)
+            .returning(|_| Err(PageTitledError(PageId::from_str("20210205T000000Z")?).into()));
+        let app = TestApp { page_repository };
+        let format = DailyTitleFormat::default();
+
+        let (page_id, created) = app
+            .daily_page_use_case()
+            .daily_page(NaiveDate::from_ymd(2021, 2, 3), &format)?;
+        assert_eq!(page_id, PageId::from_str("20210203T040507Z")?);
+        assert!(!created);
+
+        let (_, created) = app
+            .daily_page_use_case()
+            .daily_page(NaiveDate::from_ymd(2021, 2, 4), &format)?;
+        assert!(created);
+
+        let (page_id, created) = app
+            .daily_page_use_case()
+            .daily_page(NaiveDate::from_ymd(2021, 2, 5), &format)?;
+        assert_eq!(page_id, PageId::from_str("20210205T000000Z")?);
+        assert!(!created);
+        Ok(())
+    }
+}
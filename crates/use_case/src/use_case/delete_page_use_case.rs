@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use anyhow::anyhow;
+use entity::PageId;
+
+use crate::{HasPageRepository, PageRepository};
+
+pub trait DeletePageUseCase: HasPageRepository {
+    // moves the page to the trash
+    // returns the pages that link to it and the pages that obsolete it
+    fn delete_page(
+        &self,
+        page_id: &PageId,
+    ) -> anyhow::Result<(BTreeSet<PageId>, BTreeSet<PageId>)> {
+        let page_graph = self.page_repository().load_page_graph()?;
+        let title = page_graph
+            .title(page_id)
+            .ok_or_else(|| anyhow!("page not found: {}", page_id))?;
+        // the title links still work if another page has the title
+        let title_linked_by = if page_graph.titled(&title).len() > 1 {
+            BTreeSet::new()
+        } else {
+            page_graph.find_ids_link_to(&title)
+        };
+        let linked_by = title_linked_by
+            .into_iter()
+            .chain(page_graph.find_ids_link_to_page(page_id))
+            .filter(|id| id != page_id)
+            .collect::<BTreeSet<PageId>>();
+        let obsoleted_by = page_graph.obsoleted_by(page_id);
+        if !self.page_repository().trash(page_id)? {
+            return Err(anyhow!("page not found: {}", page_id));
+        }
+        Ok((linked_by, obsoleted_by))
+    }
+}
+
+impl<T: HasPageRepository> DeletePageUseCase for T {}
+
+pub trait HasDeletePageUseCase {
+    type DeletePageUseCase: DeletePageUseCase;
+
+    fn delete_page_use_case(&self) -> &Self::DeletePageUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use entity::{Page, PageContent, PageGraph};
+    use mockall::predicate;
+
+    use super::*;
+    use crate::MockPageRepository;
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasDeletePageUseCase for TestApp {
+        type DeletePageUseCase = TestApp;
+
+        fn delete_page_use_case(&self) -> &Self::DeletePageUseCase {
+            self
+        }
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let page_id1 = PageId::from_str("20210203T040506Z")?;
+        let page_id2 = PageId::from_str("20210203T040507Z")?;
+        let page_id3 = PageId::from_str("20210203T040508Z")?;
+        let mut page_repository = MockPageRepository::new();
+        page_repository.expect_load_page_graph().returning(move || {
+            let mut page_graph = PageGraph::default();
+            page_graph.add_page(Page::new(
+                page_id1,
+                PageContent::from("# foo\n\n[bar](/titles/bar)".to_string()),
+            ));
+            page_graph.add_page(Page::new(
+                page_id2,
+                PageContent::from(
+                    vec![
+                        "# foo",
+                        "",
+                        "## Obsoletes",
+                        "",
+                        "- [20210203T040506Z](/pages/20210203T040506Z)",
+                    ]
+                    .join("\n"),
+                ),
+            ));
+            page_graph.add_page(Page::new(
+                page_id3,
+                PageContent::from(
+                    "# bar\n\n[foo](/titles/foo) [foo](/pages/20210203T040507Z)".to_string(),
+                ),
+            ));
+            Ok(Arc::new(page_graph))
+        });
+        page_repository
+            .expect_trash()
+            .with(predicate::eq(page_id1))
+            .returning(|_| Ok(true));
+        page_repository
+            .expect_trash()
+            .with(predicate::eq(page_id2))
+            .returning(|_| Ok(true));
+        page_repository
+            .expect_trash()
+            .with(predicate::eq(page_id3))
+            .returning(|_| Ok(true));
+        let app = TestApp { page_repository };
+
+        // page_id2 has the same title
+        let (linked_by, obsoleted_by) = app.delete_page_use_case().delete_page(&page_id1)?;
+        assert!(linked_by.is_empty());
+        assert_eq!(obsoleted_by, vec![page_id2].into_iter().collect());
+
+        // page_id3 links to this version
+        let (linked_by, _) = app.delete_page_use_case().delete_page(&page_id2)?;
+        assert_eq!(linked_by, vec![page_id3].into_iter().collect());
+
+        let (linked_by, obsoleted_by) = app.delete_page_use_case().delete_page(&page_id3)?;
+        assert_eq!(linked_by, vec![page_id1].into_iter().collect());
+        assert!(obsoleted_by.is_empty());
+
+        assert!(app
+            .delete_page_use_case()
+            .delete_page(&PageId::from_str("20210203T040509Z")?)
+            .is_err());
+        Ok(())
+    }
+}
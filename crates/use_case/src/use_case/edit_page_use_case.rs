@@ -1,11 +1,11 @@
 use std::collections::BTreeSet;
 
 use anyhow::{anyhow, Context};
-use entity::{Page, PageId, PageIdOrPageTitle};
+use entity::{PageContent, PageId, PageIdOrPageTitle};
 
-use crate::{HasPageRepository, PageRepository};
+use crate::{CreatePageUseCase, HasPageRepository, PageRepository};
 
-pub trait EditPageUseCase: HasPageRepository {
+pub trait EditPageUseCase: CreatePageUseCase {
     fn edit_page(&self, page_id: &PageIdOrPageTitle) -> anyhow::Result<(PageId, PageId, bool)> {
         let page_graph = self.page_repository().load_page_graph()?;
         let page_id = match page_id {
@@ -28,13 +28,26 @@ pub trait EditPageUseCase: HasPageRepository {
             .find_by_id(&page_id)?
             .with_context(|| anyhow!("file not found: {}", page_id))?;
         let is_obsoleted = page_graph.is_obsoleted(&page_id);
+        // an obsoleted page can be edited explicitly (the caller warns about it)
         let mut page_content = page.content().clone();
         page_content.replace_obsoletes(page_id);
-        let new_page_id = PageId::new().context("This application is out of date.")?;
-        self.page_repository()
-            .save(Page::new(new_page_id, page_content))?;
+        let new_page_id = self.create_page(page_content)?;
         Ok((page_id, new_page_id, is_obsoleted))
     }
+
+    // saves the edited content as a new page that obsoletes the page
+    // fails with `PageObsoletedError` if the page is obsoleted (e.g. edited concurrently)
+    fn edit_page_content(
+        &self,
+        page_id: &PageId,
+        mut page_content: PageContent,
+    ) -> anyhow::Result<PageId> {
+        if self.page_repository().find_by_id(page_id)?.is_none() {
+            return Err(anyhow!("file not found: {}", page_id));
+        }
+        page_content.replace_obsoletes(*page_id);
+        self.create_page_obsoleting(page_content, page_id, None)
+    }
 }
 
 impl<T: HasPageRepository> EditPageUseCase for T {}
@@ -47,13 +60,16 @@ pub trait HasEditPageUseCase {
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+    };
 
     use entity::{Page, PageContent, PageGraph};
     use mockall::predicate;
 
     use super::*;
-    use crate::MockPageRepository;
+    use crate::{MockPageRepository, PageObsoletedError};
 
     struct TestApp {
         page_repository: MockPageRepository,
@@ -81,7 +97,7 @@ mod tests {
         let page_id = PageId::from_str("20210203T040506Z")?;
         page_repository
             .expect_load_page_graph()
-            .returning(|| Ok(PageGraph::default()));
+            .returning(|| Ok(Arc::new(PageGraph::default())));
         page_repository
             .expect_find_by_id()
             .with(predicate::eq(page_id))
@@ -92,9 +108,9 @@ mod tests {
                 )))
             });
         page_repository
-            .expect_save()
+            .expect_create()
             // TODO: test new_page_id & content
-            .returning(|_| Ok(()));
+            .returning(|_| Ok(true));
         let app = TestApp { page_repository };
         let _new_page_id = app
             .edit_page_use_case()
@@ -102,4 +118,79 @@ mod tests {
         // TODO: test _new_page_id
         Ok(())
     }
+    #[test]
+    fn edit_page_content_test() -> anyhow::Result<()> {
+        let mut page_repository = MockPageRepository::new();
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        page_repository
+            .expect_find_by_id()
+            .with(predicate::eq(page_id))
+            .returning(move |_| {
+                Ok(Some(Page::new(
+                    page_id,
+                    PageContent::from("# title\n\ncontent1".to_string()),
+                )))
+            });
+        page_repository
+            .expect_create_if_not_obsoleted()
+            .withf(move |page, obsoleted| {
+                obsoleted == &page_id
+                    && page.content().as_str()
+                        == vec![
+                            "# title",
+                            "",
+                            "content2",
+                            "",
+                            "## Obsoletes",
+                            "",
+                            "- [20210203T040506Z](/pages/20210203T040506Z)",
+                            "",
+                        ]
+                        .join("\n")
+            })
+            .returning(|_, _| Ok(true));
+        let app = TestApp { page_repository };
+        app.edit_page_use_case().edit_page_content(
+            &page_id,
+            PageContent::from("# title\n\ncontent2\n".to_string()),
+        )?;
+        Ok(())
+    }
+
+    #[test]
+    fn edit_page_content_twice_test() -> anyhow::Result<()> {
+        let mut page_repository = MockPageRepository::new();
+        let page_id = PageId::from_str("20210203T040506Z")?;
+        page_repository.expect_find_by_id().returning(move |_| {
+            Ok(Some(Page::new(
+                page_id,
+                PageContent::from("# title\n\ncontent1".to_string()),
+            )))
+        });
+        // the repository knows which pages are obsoleted by the created pages
+        let obsoleted = Arc::new(Mutex::new(BTreeSet::new()));
+        page_repository
+            .expect_create_if_not_obsoleted()
+            .returning(move |_, page_id| {
+                if !obsoleted.lock().unwrap().insert(*page_id) {
+                    return Err(PageObsoletedError(*page_id).into());
+                }
+                Ok(true)
+            });
+        let app = TestApp { page_repository };
+        // two edits of the same version (e.g. in two browsers)
+        app.edit_page_use_case().edit_page_content(
+            &page_id,
+            PageContent::from("# title\n\ncontent2\n".to_string()),
+        )?;
+        let e = app
+            .edit_page_use_case()
+            .edit_page_content(
+                &page_id,
+                PageContent::from("# title\n\ncontent3\n".to_string()),
+            )
+            .unwrap_err();
+        assert!(e.is::<PageObsoletedError>());
+        Ok(())
+    }
 }
@@ -26,7 +26,7 @@ pub trait HasListPagesUseCase {
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{str::FromStr, sync::Arc};
 
     use entity::{Page, PageContent, PageGraph, PageId};
 
@@ -59,7 +59,7 @@ mod tests {
             let mut page_repository = MockPageRepository::new();
             page_repository
                 .expect_load_page_graph()
-                .returning(|| Ok(PageGraph::default()));
+                .returning(|| Ok(Arc::new(PageGraph::default())));
             page_repository.expect_find_ids().returning(|| Ok(vec![]));
             let app = TestApp { page_repository };
             let pages = app.list_pages_use_case().list_pages(true)?;
@@ -89,7 +89,7 @@ mod tests {
                     );
                     Page::new(page_id2, page_content)
                 });
-                Ok(page_graph)
+                Ok(Arc::new(page_graph))
             });
             page_repository
                 .expect_find_ids()
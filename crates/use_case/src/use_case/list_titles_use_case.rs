@@ -44,7 +44,7 @@ pub trait HasListTitlesUseCase {
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{str::FromStr, sync::Arc};
 
     use entity::{Page, PageContent, PageGraph, PageId};
 
@@ -76,7 +76,7 @@ mod tests {
         let mut page_repository = MockPageRepository::new();
         page_repository
             .expect_load_page_graph()
-            .returning(|| Ok(PageGraph::default()));
+            .returning(|| Ok(Arc::new(PageGraph::default())));
         let app = TestApp { page_repository };
         let titles = app.list_titles_use_case().list_titles(true)?;
         assert!(titles.is_empty());
@@ -116,7 +116,7 @@ mod tests {
                 );
                 Page::new(page_id, page_content)
             });
-            Ok(page_graph)
+            Ok(Arc::new(page_graph))
         });
         let app = TestApp { page_repository };
         let titles = app.list_titles_use_case().list_titles(false)?;
@@ -157,7 +157,7 @@ mod tests {
                 );
                 Page::new(page_id, page_content)
             });
-            Ok(page_graph)
+            Ok(Arc::new(page_graph))
         });
         let app = TestApp { page_repository };
         let titles = app.list_titles_use_case().list_titles(true)?;
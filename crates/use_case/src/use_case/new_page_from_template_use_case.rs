@@ -0,0 +1,112 @@
+use anyhow::{anyhow, Context};
+use entity::{PageId, PageTitle};
+
+use crate::{
+    CreatePageUseCase, HasPageContentTemplateRepository, HasPageRepository,
+    PageContentTemplateRepository, PageRepository,
+};
+
+pub trait NewPageFromTemplateUseCase: CreatePageUseCase + HasPageContentTemplateRepository {
+    fn new_page_from_template(
+        &self,
+        page_title: PageTitle,
+        template_name: &str,
+    ) -> anyhow::Result<(PageId, bool)> {
+        let template = self
+            .page_content_template_repository()
+            .find_by_name(template_name)?
+            .with_context(|| anyhow!("template not found: {}", template_name))?;
+        let page_graph = self.page_repository().load_page_graph()?;
+        let already_exists = !page_graph.titled(&page_title).is_empty();
+        let page_id = self.create_page_with(|page_id| template.render(page_id, &page_title))?;
+        Ok((page_id, already_exists))
+    }
+}
+
+impl<T: HasPageRepository + HasPageContentTemplateRepository> NewPageFromTemplateUseCase for T {}
+
+pub trait HasNewPageFromTemplateUseCase {
+    type NewPageFromTemplateUseCase: NewPageFromTemplateUseCase;
+
+    fn new_page_from_template_use_case(&self) -> &Self::NewPageFromTemplateUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use entity::{PageContentTemplate, PageGraph};
+
+    use super::*;
+    use crate::{MockPageContentTemplateRepository, MockPageRepository};
+
+    struct TestApp {
+        page_content_template_repository: MockPageContentTemplateRepository,
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageContentTemplateRepository for TestApp {
+        type PageContentTemplateRepository = MockPageContentTemplateRepository;
+
+        fn page_content_template_repository(&self) -> &Self::PageContentTemplateRepository {
+            &self.page_content_template_repository
+        }
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasNewPageFromTemplateUseCase for TestApp {
+        type NewPageFromTemplateUseCase = TestApp;
+
+        fn new_page_from_template_use_case(&self) -> &Self::NewPageFromTemplateUseCase {
+            self
+        }
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let mut page_content_template_repository = MockPageContentTemplateRepository::new();
+        page_content_template_repository
+            .expect_find_by_name()
+            .returning(|name| {
+                Ok(match name {
+                    "book" => Some(PageContentTemplate::new(
+                        "book".to_string(),
+                        "# {{title}}\n\n[{{id}}](/pages/{{id}})\n".to_string(),
+                    )),
+                    _ => None,
+                })
+            });
+        let mut page_repository = MockPageRepository::new();
+        page_repository
+            .expect_load_page_graph()
+            .returning(|| Ok(Arc::new(PageGraph::default())));
+        page_repository
+            .expect_create()
+            .withf(|page| {
+                page.content().as_str()
+                    == format!("# title1\n\n[{0}](/pages/{0})\n", page.id()).as_str()
+            })
+            .returning(|_| Ok(true));
+        let app = TestApp {
+            page_content_template_repository,
+            page_repository,
+        };
+        let page_title = PageTitle::from("title1".to_string());
+        let (_, already_exists) = app
+            .new_page_from_template_use_case()
+            .new_page_from_template(page_title.clone(), "book")?;
+        assert!(!already_exists);
+        assert!(app
+            .new_page_from_template_use_case()
+            .new_page_from_template(page_title, "unknown")
+            .is_err());
+        Ok(())
+    }
+}
@@ -1,16 +1,14 @@
-use anyhow::Context;
-use entity::{Page, PageContent, PageId, PageTitle};
+use entity::{PageContent, PageId, PageTitle};
 
-use crate::{HasPageRepository, PageRepository};
+use crate::{CreatePageUseCase, HasPageRepository, PageRepository};
 
-pub trait NewPageUseCase: HasPageRepository {
+pub trait NewPageUseCase: CreatePageUseCase {
     fn new_page(&self, page_title: PageTitle) -> anyhow::Result<(PageId, bool)> {
         let page_graph = self.page_repository().load_page_graph()?;
         // TODO: already_exists is always true when page_title is empty
         let already_exists = !page_graph.titled(&page_title).is_empty();
-        let page_id = PageId::new().context("This application is out of date.")?;
         let content = PageContent::from(format!("# {}", page_title));
-        self.page_repository().save(Page::new(page_id, content))?;
+        let page_id = self.create_page(content)?;
         Ok((page_id, already_exists))
     }
 }
@@ -25,6 +23,8 @@ pub trait HasNewPageUseCase {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use entity::PageGraph;
 
     use super::*;
@@ -55,11 +55,11 @@ mod tests {
         let mut page_repository = MockPageRepository::new();
         page_repository
             .expect_load_page_graph()
-            .returning(|| Ok(PageGraph::default()));
+            .returning(|| Ok(Arc::new(PageGraph::default())));
         page_repository
-            .expect_save()
+            .expect_create()
             // TODO: test new_page_id & content
-            .returning(|_| Ok(()));
+            .returning(|_| Ok(true));
         let app = TestApp { page_repository };
         let page_title = PageTitle::from("title1".to_string());
         let (_new_page_id, already_exists) = app.new_page_use_case().new_page(page_title)?;
@@ -0,0 +1,286 @@
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Context};
+use entity::{Page, PageContent, PageId, PageTitle};
+
+use crate::{EditPageUseCase, HasPageRepository, PageRepository};
+
+pub trait RenameTitleUseCase: EditPageUseCase {
+    // returns the pages to rewrite and their rewritten contents (obsoleted pages are skipped)
+    fn find_title_renames(
+        &self,
+        from: &PageTitle,
+        to: &PageTitle,
+    ) -> anyhow::Result<Vec<(Page, PageContent)>> {
+        let page_graph = self.page_repository().load_page_graph()?;
+        let is_used = |page_title: &PageTitle| {
+            page_graph
+                .titled(page_title)
+                .iter()
+                .any(|page_id| !page_graph.is_obsoleted(page_id))
+        };
+        if !is_used(from) {
+            return Err(anyhow!("title not found: {}", from));
+        }
+        if is_used(to) {
+            return Err(anyhow!("title already exists: {}", to));
+        }
+        let page_ids = page_graph
+            .titled(from)
+            .into_iter()
+            .chain(page_graph.find_ids_link_to(from))
+            .filter(|page_id| !page_graph.is_obsoleted(page_id))
+            .collect::<BTreeSet<PageId>>();
+        let mut res = vec![];
+        for page_id in page_ids {
+            let page = self
+                .page_repository()
+                .find_by_id(&page_id)?
+                .with_context(|| anyhow!("file not found: {}", page_id))?;
+            let mut page_content = page.content().clone();
+            if &page_content.title() == from {
+                page_content.replace_title(to);
+            }
+            page_content.replace_title_links(from, to);
+            if &page_content != page.content() {
+                res.push((page, page_content));
+            }
+        }
+        Ok(res)
+    }
+
+    // creates the new pages that obsolete the rewritten pages
+    // returns (old page id, new page id)
+    fn rename_title(
+        &self,
+        from: &PageTitle,
+        to: &PageTitle,
+    ) -> anyhow::Result<Vec<(PageId, PageId)>> {
+        let mut res: Vec<(PageId, PageId)> = vec![];
+        for (page, mut page_content) in self.find_title_renames(from, to)? {
+            page_content.replace_obsoletes(*page.id());
+            // continues from the last id, so the ids within a second don't run out
+            let after = res.last().map(|(_, new_page_id)| new_page_id);
+            let new_page_id = self
+                .create_page_obsoleting(page_content, page.id(), after)
+                .with_context(|| {
+                    anyhow!(
+                        "failed to rewrite {} (rewritten: {})",
+                        page.id(),
+                        res.iter()
+                            .map(|(old, new)| format!("{} -> {}", old, new))
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    )
+                })?;
+            res.push((*page.id(), new_page_id));
+        }
+        Ok(res)
+    }
+}
+
+impl<T: HasPageRepository> RenameTitleUseCase for T {}
+
+pub trait HasRenameTitleUseCase {
+    type RenameTitleUseCase: RenameTitleUseCase;
+
+    fn rename_title_use_case(&self) -> &Self::RenameTitleUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+    };
+
+    use entity::PageGraph;
+
+    use super::*;
+    use crate::MockPageRepository;
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasRenameTitleUseCase for TestApp {
+        type RenameTitleUseCase = TestApp;
+
+        fn rename_title_use_case(&self) -> &Self::RenameTitleUseCase {
+            self
+        }
+    }
+
+    fn pages() -> anyhow::Result<Vec<Page>> {
+        Ok(vec![
+            Page::new(
+                PageId::from_str("20210203T040506Z")?,
+                PageContent::from(vec!["# foo", "", "content1", ""].join("\n")),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040507Z")?,
+                PageContent::from(vec!["# bar", "", "[foo](/titles/foo)", ""].join("\n")),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040508Z")?,
+                PageContent::from(vec!["# baz", "", "[bar](/titles/bar)", ""].join("\n")),
+            ),
+        ])
+    }
+
+    fn page_repository() -> MockPageRepository {
+        let mut page_repository = MockPageRepository::new();
+        page_repository.expect_load_page_graph().returning(|| {
+            let mut page_graph = PageGraph::default();
+            for page in pages()? {
+                page_graph.add_page(page);
+            }
+            Ok(Arc::new(page_graph))
+        });
+        page_repository
+            .expect_find_by_id()
+            .returning(|page_id| Ok(pages()?.into_iter().find(|page| page.id() == page_id)));
+        page_repository
+    }
+
+    #[test]
+    fn find_title_renames_test() -> anyhow::Result<()> {
+        let app = TestApp {
+            page_repository: page_repository(),
+        };
+        let foo = PageTitle::from("foo".to_string());
+        let qux = PageTitle::from("qux".to_string());
+        let renames = app.rename_title_use_case().find_title_renames(&foo, &qux)?;
+        assert_eq!(
+            renames
+                .into_iter()
+                .map(|(page, page_content)| (page.id().to_string(), page_content.to_string()))
+                .collect::<Vec<(String, String)>>(),
+            vec![
+                (
+                    "20210203T040506Z".to_string(),
+                    vec!["# qux", "", "content1", ""].join("\n")
+                ),
+                (
+                    "20210203T040507Z".to_string(),
+                    vec!["# bar", "", "[qux](/titles/qux)", ""].join("\n")
+                ),
+            ]
+        );
+
+        let bar = PageTitle::from("bar".to_string());
+        assert!(app
+            .rename_title_use_case()
+            .find_title_renames(&foo, &bar)
+            .is_err());
+        assert!(app
+            .rename_title_use_case()
+            .find_title_renames(&qux, &foo)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rename_title_test() -> anyhow::Result<()> {
+        let mut page_repository = page_repository();
+        let created = Arc::new(Mutex::new(vec![]));
+        let created_in_mock = created.clone();
+        page_repository
+            .expect_create_if_not_obsoleted()
+            .returning(move |page, _| {
+                created_in_mock.lock().unwrap().push(page);
+                Ok(true)
+            });
+        let app = TestApp { page_repository };
+        let renamed = app.rename_title_use_case().rename_title(
+            &PageTitle::from("bar".to_string()),
+            &PageTitle::from("qux".to_string()),
+        )?;
+        let created = created.lock().unwrap().clone();
+        assert_eq!(
+            renamed,
+            vec![
+                (PageId::from_str("20210203T040507Z")?, *created[0].id()),
+                (PageId::from_str("20210203T040508Z")?, *created[1].id()),
+            ]
+        );
+        assert_eq!(
+            created[1].content().to_string(),
+            vec![
+                "# baz",
+                "",
+                "[qux](/titles/qux)",
+                "",
+                "## Obsoletes",
+                "",
+                "- [20210203T040508Z](/pages/20210203T040508Z)",
+                "",
+            ]
+            .join("\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rename_title_many_referrers_test() -> anyhow::Result<()> {
+        fn pages() -> anyhow::Result<Vec<Page>> {
+            let mut pages = vec![Page::new(
+                PageId::from_str("20210203T040000Z")?,
+                PageContent::from(vec!["# foo", ""].join("\n")),
+            )];
+            for i in 1..=70 {
+                pages.push(Page::new(
+                    PageId::from_str(&format!("20210203T04{:02}{:02}Z", i / 60, i % 60))?,
+                    PageContent::from(vec!["# bar", "", "[foo](/titles/foo)", ""].join("\n")),
+                ));
+            }
+            Ok(pages)
+        }
+
+        let mut page_repository = MockPageRepository::new();
+        page_repository.expect_load_page_graph().returning(|| {
+            let mut page_graph = PageGraph::default();
+            for page in pages()? {
+                page_graph.add_page(page);
+            }
+            Ok(Arc::new(page_graph))
+        });
+        page_repository
+            .expect_find_by_id()
+            .returning(|page_id| Ok(pages()?.into_iter().find(|page| page.id() == page_id)));
+        let created = Arc::new(Mutex::new(BTreeSet::new()));
+        let created_in_mock = created.clone();
+        page_repository
+            .expect_create_if_not_obsoleted()
+            .returning(move |page, _| {
+                let mut created = created_in_mock.lock().unwrap();
+                if created.len() == 65 {
+                    return Err(anyhow!("disk full"));
+                }
+                Ok(created.insert(*page.id()))
+            });
+        let app = TestApp { page_repository };
+        let foo = PageTitle::from("foo".to_string());
+        let qux = PageTitle::from("qux".to_string());
+        let err = app
+            .rename_title_use_case()
+            .rename_title(&foo, &qux)
+            .unwrap_err();
+        // more than 60 pages are created within a second
+        assert_eq!(created.lock().unwrap().len(), 65);
+        let message = err.to_string();
+        assert!(message.starts_with("failed to rewrite 20210203T040105Z (rewritten: "));
+        for i in 0..65 {
+            assert!(message.contains(&format!("20210203T04{:02}{:02}Z -> ", i / 60, i % 60)));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet},
+};
+
+use entity::{LineNumber, PageId, Query, QueryMatch, SearchHit, Snippet};
+
+use crate::{HasPageRepository, PageRepository};
+
+const TITLE_MATCH_WEIGHT: usize = 10;
+const BACKLINK_WEIGHT: usize = 2;
+
+pub trait SearchPagesUseCase: HasPageRepository {
+    // sorted by score (term frequency, title match and backlink count)
+    fn search_pages(&self, query: &Query, all: bool) -> anyhow::Result<Vec<SearchHit>> {
+        let page_graph = self.page_repository().load_page_graph()?;
+        let mut grouped: BTreeMap<PageId, Vec<QueryMatch>> = BTreeMap::new();
+        for (page_id, query_match) in self.page_repository().find_by_query(query)? {
+            if !all && page_graph.is_obsoleted(&page_id) {
+                continue;
+            }
+            grouped.entry(page_id).or_default().push(query_match);
+        }
+
+        let mut hits = vec![];
+        for (page_id, matches) in grouped {
+            let page = match self.page_repository().find_by_id(&page_id)? {
+                Some(page) => page,
+                None => continue,
+            };
+            let title = page.title();
+            let term_frequency = matches
+                .iter()
+                .filter(|m| !m.byte_range().is_empty())
+                .count();
+            let title_match = !query.matches(title.as_str()).is_empty();
+            let backlinks = page_graph
+                .find_ids_link_to(&title)
+                .iter()
+                .filter(|id| !page_graph.is_obsoleted(id))
+                .count();
+            let score = term_frequency
+                + if title_match { TITLE_MATCH_WEIGHT } else { 0 }
+                + backlinks * BACKLINK_WEIGHT;
+
+            let lines = page.content().as_str().lines().collect::<Vec<&str>>();
+            let mut snippets = vec![];
+            let line_numbers = matches
+                .iter()
+                .map(|m| m.line_number())
+                .collect::<BTreeSet<LineNumber>>();
+            for line_number in line_numbers {
+                let line = lines
+                    .get(usize::from(line_number) - 1)
+                    .copied()
+                    .unwrap_or_default();
+                let byte_ranges = matches
+                    .iter()
+                    .filter(|m| m.line_number() == line_number)
+                    .map(|m| m.byte_range().clone())
+                    .collect::<Vec<_>>();
+                snippets.push(Snippet::new(line_number, line, &byte_ranges));
+            }
+
+            hits.push(SearchHit::new(
+                page_id,
+                title,
+                page_graph.is_obsoleted(&page_id),
+                score,
+                snippets,
+            ));
+        }
+        hits.sort_by_key(|hit| (Reverse(hit.score()), Reverse(*hit.page_id())));
+        Ok(hits)
+    }
+}
+
+impl<T: HasPageRepository> SearchPagesUseCase for T {}
+
+pub trait HasSearchPagesUseCase {
+    type SearchPagesUseCase: SearchPagesUseCase;
+
+    fn search_pages_use_case(&self) -> &Self::SearchPagesUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{str::FromStr, sync::Arc};
+
+    use entity::{Page, PageContent, PageGraph};
+
+    use super::*;
+    use crate::MockPageRepository;
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasSearchPagesUseCase for TestApp {
+        type SearchPagesUseCase = TestApp;
+
+        fn search_pages_use_case(&self) -> &Self::SearchPagesUseCase {
+            self
+        }
+    }
+
+    fn pages() -> anyhow::Result<Vec<Page>> {
+        Ok(vec![
+            Page::new(
+                PageId::from_str("20210203T040506Z")?,
+                PageContent::from(vec!["# foo", "", "bar", "", "foo foo foo", ""].join("\n")),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040507Z")?,
+                PageContent::from(vec!["# bar", "", "foo", ""].join("\n")),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040508Z")?,
+                PageContent::from(
+                    vec!["# baz", "", "[bar]", "", "[bar]: /titles/bar", ""].join("\n"),
+                ),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040509Z")?,
+                PageContent::from(
+                    vec!["# qux", "", "[bar]", "", "[bar]: /titles/bar", ""].join("\n"),
+                ),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test() -> anyhow::Result<()> {
+        let mut page_repository = MockPageRepository::new();
+        page_repository.expect_load_page_graph().returning(|| {
+            let mut page_graph = PageGraph::default();
+            for page in pages()? {
+                page_graph.add_page(page);
+            }
+            Ok(Arc::new(page_graph))
+        });
+        page_repository.expect_find_by_query().returning(|query| {
+            let mut res = vec![];
+            for page in pages()? {
+                for m in query.matches(page.content().as_str()) {
+                    res.push((*page.id(), m));
+                }
+            }
+            Ok(res)
+        });
+        page_repository
+            .expect_find_by_id()
+            .returning(|page_id| Ok(pages()?.into_iter().find(|page| page.id() == page_id)));
+        let app = TestApp { page_repository };
+
+        let hits = app
+            .search_pages_use_case()
+            .search_pages(&Query::from_str("bar")?, false)?;
+        assert_eq!(
+            hits.iter()
+                .map(|hit| (hit.page_id().to_string(), hit.score()))
+                .collect::<Vec<_>>(),
+            vec![
+                // 1 (term frequency) + 10 (title match) + 2 * 2 (backlinks)
+                ("20210203T040507Z".to_string(), 15),
+                ("20210203T040509Z".to_string(), 3),
+                ("20210203T040508Z".to_string(), 3),
+                ("20210203T040506Z".to_string(), 1),
+            ]
+        );
+        assert_eq!(hits[0].title().as_str(), "bar");
+        assert_eq!(
+            hits[0].snippet().map(|snippet| snippet.text()),
+            Some("# bar")
+        );
+        assert_eq!(hits[3].snippet().map(|snippet| snippet.text()), Some("bar"));
+        assert!(!hits[3].is_obsoleted());
+
+        let hits = app
+            .search_pages_use_case()
+            .search_pages(&Query::from_str("foo")?, false)?;
+        assert_eq!(hits[0].page_id().to_string(), "20210203T040506Z");
+        assert_eq!(
+            hits[0]
+                .snippets()
+                .iter()
+                .map(|snippet| usize::from(snippet.line_number()))
+                .collect::<Vec<usize>>(),
+            vec![1, 5]
+        );
+        assert_eq!(
+            hits[0].snippets()[1].fragments(),
+            vec![
+                ("foo", true),
+                (" ", false),
+                ("foo", true),
+                (" ", false),
+                ("foo", true)
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use entity::{PageId, PageTitle, Query, TextSpan};
+
+use crate::{EditPageUseCase, HasPageRepository, PageRepository};
+
+pub trait UnlinkedMentionsUseCase: EditPageUseCase {
+    // the pages that mention the title without linking it (obsoleted pages are skipped)
+    fn find_unlinked_mentions(
+        &self,
+        page_title: &PageTitle,
+    ) -> anyhow::Result<Vec<(PageId, Vec<TextSpan>)>> {
+        let page_graph = self.page_repository().load_page_graph()?;
+        // narrows the pages down with the search index before reading them
+        // (the query has no escape for quotes, so the title is split at them)
+        let terms = page_title
+            .as_str()
+            .split('"')
+            .filter(|s| !s.trim().is_empty())
+            .map(|s| format!("\"{}\"", s))
+            .collect::<Vec<String>>();
+        let page_ids = if terms.is_empty() {
+            self.page_repository().find_ids()?
+        } else {
+            let query = Query::from_str(terms.join(" ").as_str())?;
+            self.page_repository().find_candidate_ids(&query)?
+        };
+        let mut res = vec![];
+        for page_id in page_ids {
+            if page_graph.is_obsoleted(&page_id) {
+                continue;
+            }
+            let page = match self.page_repository().find_by_id(&page_id)? {
+                Some(page) => page,
+                None => continue,
+            };
+            if &page.title() == page_title {
+                continue;
+            }
+            let mentions = page.content().mentions(page_title);
+            if !mentions.is_empty() {
+                res.push((page_id, mentions));
+            }
+        }
+        Ok(res)
+    }
+
+    // creates a new page that links the mentions and obsoletes the page
+    // fails with `PageObsoletedError` if the page is obsoleted (e.g. linked twice)
+    fn link_mentions(&self, page_id: &PageId, page_title: &PageTitle) -> anyhow::Result<PageId> {
+        let page = self
+            .page_repository()
+            .find_by_id(page_id)?
+            .with_context(|| anyhow!("file not found: {}", page_id))?;
+        let mut page_content = page.content().clone();
+        page_content.link_mentions(page_title);
+        if &page_content == page.content() {
+            return Err(anyhow!(
+                "no unlinked mentions of {} in {}",
+                page_title,
+                page_id
+            ));
+        }
+        self.edit_page_content(page_id, page_content)
+    }
+}
+
+impl<T: HasPageRepository> UnlinkedMentionsUseCase for T {}
+
+pub trait HasUnlinkedMentionsUseCase {
+    type UnlinkedMentionsUseCase: UnlinkedMentionsUseCase;
+
+    fn unlinked_mentions_use_case(&self) -> &Self::UnlinkedMentionsUseCase;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        str::FromStr,
+        sync::{Arc, Mutex},
+    };
+
+    use entity::{Page, PageContent, PageGraph};
+
+    use super::*;
+    use crate::{MockPageRepository, PageObsoletedError};
+
+    struct TestApp {
+        page_repository: MockPageRepository,
+    }
+
+    impl HasPageRepository for TestApp {
+        type PageRepository = MockPageRepository;
+
+        fn page_repository(&self) -> &Self::PageRepository {
+            &self.page_repository
+        }
+    }
+
+    impl HasUnlinkedMentionsUseCase for TestApp {
+        type UnlinkedMentionsUseCase = TestApp;
+
+        fn unlinked_mentions_use_case(&self) -> &Self::UnlinkedMentionsUseCase {
+            self
+        }
+    }
+
+    fn pages() -> anyhow::Result<Vec<Page>> {
+        Ok(vec![
+            Page::new(
+                PageId::from_str("20210203T040506Z")?,
+                PageContent::from(vec!["# foo", "", "foo", ""].join("\n")),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040507Z")?,
+                PageContent::from(vec!["# bar", "", "foo and [foo](/titles/foo)", ""].join("\n")),
+            ),
+            Page::new(
+                PageId::from_str("20210203T040508Z")?,
+                PageContent::from(vec!["# baz", "", "[foo](/titles/foo)", ""].join("\n")),
+            ),
+        ])
+    }
+
+    fn page_repository() -> MockPageRepository {
+        let mut page_repository = MockPageRepository::new();
+        page_repository.expect_load_page_graph().returning(|| {
+            let mut page_graph = PageGraph::default();
+            for page in pages()? {
+                page_graph.add_page(page);
+            }
+            Ok(Arc::new(page_graph))
+        });
+        page_repository
+            .expect_find_candidate_ids()
+            .withf(|query| query.to_string() == "\"foo\"")
+            .returning(|_| Ok(pages()?.iter().map(|page| *page.id()).collect()));
+        page_repository
+            .expect_find_by_id()
+            .returning(|page_id| Ok(pages()?.into_iter().find(|page| page.id() == page_id)));
+        page_repository
+    }
+
+    #[test]
+    fn find_unlinked_mentions_test() -> anyhow::Result<()> {
+        let app = TestApp {
+            page_repository: page_repository(),
+        };
+        let mentions = app
+            .unlinked_mentions_use_case()
+            .find_unlinked_mentions(&PageTitle::from("foo".to_string()))?;
+        assert_eq!(
+            mentions
+                .iter()
+                .map(|(page_id, spans)| (page_id.to_string(), spans.len()))
+                .collect::<Vec<(String, usize)>>(),
+            vec![("20210203T040507Z".to_string(), 1)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn link_mentions_test() -> anyhow::Result<()> {
+        let mut page_repository = page_repository();
+        let created = Arc::new(Mutex::new(vec![]));
+        let created_in_mock = created.clone();
+        page_repository
+            .expect_create_if_not_obsoleted()
+            .returning(move |page, _| {
+                created_in_mock.lock().unwrap().push(page);
+                Ok(true)
+            });
+        let app = TestApp { page_repository };
+        let foo = PageTitle::from("foo".to_string());
+        let page_id = PageId::from_str("20210203T040507Z")?;
+        let new_page_id = app
+            .unlinked_mentions_use_case()
+            .link_mentions(&page_id, &foo)?;
+        let created = created.lock().unwrap().clone();
+        assert_eq!(created[0].id(), &new_page_id);
+        assert_eq!(
+            created[0].content().to_string(),
+            vec![
+                "# bar",
+                "",
+                "[foo](/titles/foo) and [foo](/titles/foo)",
+                "",
+                "## Obsoletes",
+                "",
+                "- [20210203T040507Z](/pages/20210203T040507Z)",
+                "",
+            ]
+            .join("\n")
+        );
+
+        assert!(app
+            .unlinked_mentions_use_case()
+            .link_mentions(&PageId::from_str("20210203T040508Z")?, &foo)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn link_mentions_obsoleted_test() -> anyhow::Result<()> {
+        let mut page_repository = page_repository();
+        // e.g. the "link" button is clicked twice
+        page_repository
+            .expect_create_if_not_obsoleted()
+            .returning(|_, page_id| Err(PageObsoletedError(*page_id).into()));
+        let app = TestApp { page_repository };
+        let e = app
+            .unlinked_mentions_use_case()
+            .link_mentions(
+                &PageId::from_str("20210203T040507Z")?,
+                &PageTitle::from("foo".to_string()),
+            )
+            .unwrap_err();
+        assert!(e.is::<PageObsoletedError>());
+        Ok(())
+    }
+}